@@ -0,0 +1,178 @@
+//! # iso_iec_7064::alphabet
+//!
+//! The _Alphabets_ used by the _Check Character Systems_ appearing in The Standard.
+
+/// There are five _Alphabets_ used by the _Check Character Systems_ defined in The Standard. Each
+/// consists of the symbols allowed in the payload, plus any _Supplementary Check Characters_ that
+/// may only appear as a check character ('X' for MOD 11-2 and '*' for MOD 37-2).
+///
+/// While The Standard does not specify a character encoding, we will be using the ASCII subset of
+/// Unicode, so every symbol of every _Alphabet_ corresponds to a single byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Alphabet {
+    /// The digits '0' to '9', with values 0 to 9.
+    Numeric,
+    /// The digits '0' to '9', with values 0 to 9, plus the supplementary check character 'X',
+    /// with value 10.
+    NumericWithX,
+    /// The upper-case letters 'A' to 'Z', with values 0 to 25.
+    Alphabetic,
+    /// The digits '0' to '9', with values 0 to 9, and the upper-case letters 'A' to 'Z', with
+    /// values 10 to 35.
+    Alphanumeric,
+    /// The digits '0' to '9', with values 0 to 9, and the upper-case letters 'A' to 'Z', with
+    /// values 10 to 35, plus the supplementary check character '*', with value 36.
+    AlphanumericWithAsterisk,
+}
+
+impl Alphabet {
+    /// All the _Alphabets_ defined in The Standard.
+    pub const ALL: [Alphabet; 5] = [
+        Alphabet::Numeric,
+        Alphabet::NumericWithX,
+        Alphabet::Alphabetic,
+        Alphabet::Alphanumeric,
+        Alphabet::AlphanumericWithAsterisk,
+    ];
+
+    /// Look up the _Alphabet_ with the given maximum digit value, which is how the alphabet is
+    /// identified by the `MAX_DIGIT_VALUE` constant generic parameter of the `System` trait.
+    pub const fn from_max_digit_value(max_digit_value: u8) -> Option<Alphabet> {
+        match max_digit_value {
+            9 => Some(Alphabet::Numeric),
+            10 => Some(Alphabet::NumericWithX),
+            25 => Some(Alphabet::Alphabetic),
+            35 => Some(Alphabet::Alphanumeric),
+            36 => Some(Alphabet::AlphanumericWithAsterisk),
+            _ => None,
+        }
+    }
+
+    /// The largest value of any symbol of the _Alphabet_, including supplementary check
+    /// characters.
+    pub const fn max_digit_value(self) -> u8 {
+        match self {
+            Alphabet::Numeric => 9,
+            Alphabet::NumericWithX => 10,
+            Alphabet::Alphabetic => 25,
+            Alphabet::Alphanumeric => 35,
+            Alphabet::AlphanumericWithAsterisk => 36,
+        }
+    }
+
+    /// The symbols allowed anywhere in an input string, in order of their values.
+    pub const fn payload_symbols(self) -> &'static str {
+        match self {
+            Alphabet::Numeric | Alphabet::NumericWithX => "0123456789",
+            Alphabet::Alphabetic => "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            Alphabet::Alphanumeric | Alphabet::AlphanumericWithAsterisk => {
+                "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ"
+            }
+        }
+    }
+
+    /// The _Supplementary Check Characters_, which are only allowed as a check character.
+    pub const fn supplementary_symbols(self) -> &'static str {
+        match self {
+            Alphabet::NumericWithX => "X",
+            Alphabet::AlphanumericWithAsterisk => "*",
+            _ => "",
+        }
+    }
+
+    /// The value of the supplementary check character, if the _Alphabet_ has one.
+    pub const fn supplementary_value(self) -> Option<u8> {
+        match self {
+            Alphabet::NumericWithX => Some(10),
+            Alphabet::AlphanumericWithAsterisk => Some(36),
+            _ => None,
+        }
+    }
+
+    /// The number of payload symbols. This is also the radix in which two check characters
+    /// represent a checksum.
+    pub const fn radix(self) -> u8 {
+        self.payload_symbols().len() as u8
+    }
+
+    /// Convert an input ASCII character into its corresponding numeric value, returning `None` if
+    /// the character is not a symbol of the _Alphabet_.
+    pub const fn char_value(self, c: u8) -> Option<u8> {
+        match (self, c) {
+            (_, b'0'..=b'9') if !matches!(self, Alphabet::Alphabetic) => Some(c - b'0'),
+            (Alphabet::NumericWithX, b'X') => Some(10),
+            (Alphabet::Alphabetic, b'A'..=b'Z') => Some(c - b'A'),
+            (Alphabet::Alphanumeric | Alphabet::AlphanumericWithAsterisk, b'A'..=b'Z') => {
+                Some(c - b'A' + 10)
+            }
+            (Alphabet::AlphanumericWithAsterisk, b'*') => Some(36),
+            _ => None,
+        }
+    }
+
+    /// Convert a numeric value into its corresponding ASCII character, returning `None` if the
+    /// value is out of range for the _Alphabet_.
+    pub const fn value_char(self, v: u8) -> Option<u8> {
+        let payload_symbols = self.payload_symbols().as_bytes();
+        if (v as usize) < payload_symbols.len() {
+            return Some(payload_symbols[v as usize]);
+        }
+        match self.supplementary_value() {
+            Some(value) if value == v => Some(self.supplementary_symbols().as_bytes()[0]),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the ASCII character is allowed in the payload portion of an input string.
+    pub const fn is_payload_char(self, c: u8) -> bool {
+        match self.char_value(c) {
+            Some(v) => v < self.radix(),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alphabet::Alphabet;
+    use crate::*;
+
+    #[test]
+    fn values_and_chars_round_trip() {
+        for alphabet in Alphabet::ALL {
+            for v in 0..=alphabet.max_digit_value() {
+                let c = alphabet.value_char(v).unwrap();
+                assert_eq!(Some(v), alphabet.char_value(c));
+            }
+            assert_eq!(None, alphabet.value_char(alphabet.max_digit_value() + 1));
+        }
+    }
+
+    #[test]
+    fn supplementary_chars_are_not_payload_chars() {
+        assert!(Alphabet::NumericWithX.is_payload_char(b'9'));
+        assert!(!Alphabet::NumericWithX.is_payload_char(b'X'));
+        assert!(!Alphabet::AlphanumericWithAsterisk.is_payload_char(b'*'));
+        assert!(!Alphabet::Alphanumeric.is_payload_char(b'a'));
+        assert!(!Alphabet::Alphabetic.is_payload_char(b'0'));
+    }
+
+    fn assert_consistent<const MAX_DIGIT_VALUE: u8>(system: &impl System<MAX_DIGIT_VALUE>) {
+        assert_eq!(
+            Some(system.alphabet()),
+            Alphabet::from_max_digit_value(MAX_DIGIT_VALUE)
+        );
+    }
+
+    #[test]
+    fn systems_use_alphabet_matching_max_digit_value() {
+        assert_consistent(&MOD_11_2);
+        assert_consistent(&MOD_37_2);
+        assert_consistent(&MOD_97_10);
+        assert_consistent(&MOD_661_26);
+        assert_consistent(&MOD_1271_36);
+        assert_consistent(&MOD_11_10);
+        assert_consistent(&MOD_27_26);
+        assert_consistent(&MOD_37_36);
+    }
+}
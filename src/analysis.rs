@@ -0,0 +1,578 @@
+//! # iso_iec_7064::analysis
+//!
+//! Measurement of the error-detection capability of a _Check Character System_.
+//!
+//! The Standard tabulates the proportion of each class of transcription error that its systems
+//! detect. `analyze` measures these rates for any `System` and payload length, by applying every
+//! error of each class to valid strings and counting how many of the results fail the check. The
+//! valid strings are either all the payloads of the given length (`Coverage::Exhaustive`) or
+//! payloads drawn by a deterministic pseudo-random generator (`Coverage::Sampled`):
+//!
+//! ```
+//! use iso_iec_7064::analysis::{analyze, Coverage, ErrorClass};
+//! use iso_iec_7064::MOD_97_10;
+//!
+//! let report = analyze(&MOD_97_10, 3, Coverage::Exhaustive);
+//! assert_eq!(1000, report.payloads);
+//! assert_eq!(Some(1.0), report.class(ErrorClass::SingleSubstitution).rate());
+//! ```
+//!
+//! Errors are applied anywhere in the complete string, including the check character(s). A
+//! variant that is not even well-formed (for example, one that moves a _Supplementary Check
+//! Character_ into the payload) fails the check, so it counts as detected.
+
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use crate::random::Rng;
+use crate::system::System;
+
+/// A class of transcription error, as tabulated in The Standard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ErrorClass {
+    /// One character replaced by another: a → b.
+    SingleSubstitution,
+    /// Two adjacent characters swapped: ab → ba.
+    AdjacentTransposition,
+    /// Two characters one position apart swapped: abc → cba.
+    JumpTransposition,
+    /// A doubled character replaced by another doubled character: aa → bb.
+    Twin,
+    /// Equal characters one position apart both replaced: aca → bcb.
+    JumpTwin,
+    /// Confusion of similar-sounding numbers, such as "fifty" and "fifteen": a0 ↔ 1a for a from
+    /// 2 to 9. Only applies to numeric payloads.
+    Phonetic,
+}
+
+impl ErrorClass {
+    /// All the error classes.
+    pub const ALL: [ErrorClass; 6] = [
+        ErrorClass::SingleSubstitution,
+        ErrorClass::AdjacentTransposition,
+        ErrorClass::JumpTransposition,
+        ErrorClass::Twin,
+        ErrorClass::JumpTwin,
+        ErrorClass::Phonetic,
+    ];
+
+    /// A human-readable name for the error class.
+    pub const fn name(self) -> &'static str {
+        match self {
+            ErrorClass::SingleSubstitution => "single substitution",
+            ErrorClass::AdjacentTransposition => "adjacent transposition",
+            ErrorClass::JumpTransposition => "jump transposition",
+            ErrorClass::Twin => "twin",
+            ErrorClass::JumpTwin => "jump twin",
+            ErrorClass::Phonetic => "phonetic",
+        }
+    }
+}
+
+impl fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The symbols that may appear at each position of a complete string: the payload symbols, plus
+/// the _Supplementary Check Characters_ in the check character positions.
+pub(crate) struct Symbols {
+    payload: Vec<u8>,
+    check: Vec<u8>,
+    check_start: usize,
+}
+
+impl Symbols {
+    pub(crate) fn new<const MAX_DIGIT_VALUE: u8, S>(system: &S, length: usize) -> Symbols
+    where
+        S: System<MAX_DIGIT_VALUE> + ?Sized,
+    {
+        let alphabet = system.alphabet();
+        let payload = Vec::from(alphabet.payload_symbols().as_bytes());
+        let mut check = payload.clone();
+        check.extend_from_slice(alphabet.supplementary_symbols().as_bytes());
+        Symbols {
+            payload,
+            check,
+            check_start: length.saturating_sub(system.check_length() as usize),
+        }
+    }
+
+    fn at(&self, position: usize) -> &[u8] {
+        if position < self.check_start {
+            &self.payload
+        } else {
+            &self.check
+        }
+    }
+
+    /// Payload digits, for phonetic errors.
+    fn is_numeric(&self) -> bool {
+        self.payload == b"0123456789"
+    }
+}
+
+/// Call `f` with every variant of `string` produced by one error of the class. The string is
+/// modified in place while `f` runs, and restored afterwards.
+pub(crate) fn for_each_variant<F>(class: ErrorClass, string: &mut [u8], symbols: &Symbols, mut f: F)
+where
+    F: FnMut(&[u8]),
+{
+    let length = string.len();
+    match class {
+        ErrorClass::SingleSubstitution => {
+            for i in 0..length {
+                let original = string[i];
+                for &c in symbols.at(i) {
+                    if c != original {
+                        string[i] = c;
+                        f(string);
+                    }
+                }
+                string[i] = original;
+            }
+        }
+        ErrorClass::AdjacentTransposition | ErrorClass::JumpTransposition => {
+            let gap = if class == ErrorClass::AdjacentTransposition {
+                1
+            } else {
+                2
+            };
+            for i in gap..length {
+                if string[i - gap] != string[i] {
+                    string.swap(i - gap, i);
+                    f(string);
+                    string.swap(i - gap, i);
+                }
+            }
+        }
+        ErrorClass::Twin | ErrorClass::JumpTwin => {
+            let gap = if class == ErrorClass::Twin { 1 } else { 2 };
+            for i in gap..length {
+                let original = string[i];
+                if string[i - gap] != original {
+                    continue;
+                }
+                // Replacements must be allowed in both positions.
+                for &c in symbols.at(i - gap) {
+                    if c != original && symbols.at(i).contains(&c) {
+                        string[i - gap] = c;
+                        string[i] = c;
+                        f(string);
+                    }
+                }
+                string[i - gap] = original;
+                string[i] = original;
+            }
+        }
+        ErrorClass::Phonetic => {
+            if !symbols.is_numeric() {
+                return;
+            }
+            for i in 1..length {
+                let (a, b) = (string[i - 1], string[i]);
+                let replacement = match (a, b) {
+                    (b'2'..=b'9', b'0') => (b'1', a),
+                    (b'1', b'2'..=b'9') => (b, b'0'),
+                    _ => continue,
+                };
+                (string[i - 1], string[i]) = replacement;
+                f(string);
+                (string[i - 1], string[i]) = (a, b);
+            }
+        }
+    }
+}
+
+/// Which valid strings to apply the errors to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Coverage {
+    /// Every payload of the given length. The number of payloads grows exponentially with the
+    /// length, so this is only practical for short payloads.
+    Exhaustive,
+    /// The given number of payloads, drawn by a pseudo-random generator from the given seed. The
+    /// same seed always gives the same payloads.
+    Sampled { samples: u64, seed: u64 },
+}
+
+/// The detection rate measured for one error class.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ClassReport {
+    pub class: ErrorClass,
+    /// The number of erroneous variants checked.
+    pub trials: u64,
+    /// The number of those variants that failed the check.
+    pub detected: u64,
+}
+
+impl ClassReport {
+    /// The proportion of erroneous variants detected, or `None` if the error class does not apply
+    /// (for example, phonetic errors in alphabetic strings).
+    pub fn rate(&self) -> Option<f64> {
+        if self.trials == 0 {
+            None
+        } else {
+            Some(self.detected as f64 / self.trials as f64)
+        }
+    }
+
+    /// The number of erroneous variants that satisfied the check.
+    pub fn undetected(&self) -> u64 {
+        self.trials - self.detected
+    }
+}
+
+/// The detection rates measured for a system and payload length.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Report {
+    pub system_name: &'static str,
+    pub payload_length: usize,
+    pub coverage: Coverage,
+    /// The number of valid strings the errors were applied to.
+    pub payloads: u64,
+    /// One entry per error class, in the order of `ErrorClass::ALL`.
+    pub classes: [ClassReport; 6],
+}
+
+impl Report {
+    /// The measurement for one error class.
+    pub fn class(&self, class: ErrorClass) -> &ClassReport {
+        &self.classes[class as usize]
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}, payload length {}, {} payloads ({})",
+            self.system_name,
+            self.payload_length,
+            self.payloads,
+            match self.coverage {
+                Coverage::Exhaustive => "exhaustive",
+                Coverage::Sampled { .. } => "sampled",
+            }
+        )?;
+        for class in &self.classes {
+            match class.rate() {
+                Some(rate) => writeln!(
+                    f,
+                    "  {:<24}{:>12} / {:<12}{:>8.3}%",
+                    class.class.name(),
+                    class.detected,
+                    class.trials,
+                    rate * 100.0
+                )?,
+                None => writeln!(f, "  {:<24}{:>12}", class.class.name(), "n/a")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// SplitMix64, a small and fast pseudo-random generator that is good enough for sampling.
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Measure the detection rates of the system for payloads of the given length. Panics if
+/// `Coverage::Exhaustive` is requested and the number of payloads does not fit in a `u64`.
+pub fn analyze<const MAX_DIGIT_VALUE: u8, S>(
+    system: &S,
+    payload_length: usize,
+    coverage: Coverage,
+) -> Report
+where
+    S: System<MAX_DIGIT_VALUE> + ?Sized,
+{
+    let alphabet = system.alphabet();
+    let payload_symbols = alphabet.payload_symbols().as_bytes();
+    let radix = payload_symbols.len();
+    let check_length = system.check_length() as usize;
+    let symbols = Symbols::new(system, payload_length + check_length);
+
+    let mut classes = ErrorClass::ALL.map(|class| ClassReport {
+        class,
+        trials: 0,
+        detected: 0,
+    });
+    let mut payloads = 0;
+    let mut string = Vec::with_capacity(payload_length + check_length);
+
+    let mut measure = |payload: &[u8], classes: &mut [ClassReport; 6]| -> bool {
+        let check = match system
+            .checksum_ascii_bytes_iter(payload.iter().copied())
+            .and_then(|checksum| system.check_chars(checksum))
+        {
+            Some(check) => check,
+            None => return false,
+        };
+        string.clear();
+        string.extend_from_slice(payload);
+        string.extend_from_slice(check.as_bytes());
+        for report in classes.iter_mut() {
+            for_each_variant(report.class, &mut string, &symbols, |variant| {
+                report.trials += 1;
+                if !system.validate_ascii_bytes_iter(variant.iter().copied()) {
+                    report.detected += 1;
+                }
+            });
+        }
+        true
+    };
+
+    let mut payload = Vec::with_capacity(payload_length);
+    match coverage {
+        Coverage::Exhaustive => {
+            let count = u64::try_from(radix)
+                .ok()
+                .and_then(|radix| radix.checked_pow(u32::try_from(payload_length).ok()?))
+                .expect("too many payloads to analyze exhaustively");
+            let mut digits = alloc::vec![0usize; payload_length];
+            for _ in 0..count {
+                payload.clear();
+                payload.extend(digits.iter().map(|&d| payload_symbols[d]));
+                payloads += u64::from(measure(&payload, &mut classes));
+                // Advance to the next payload, like an odometer.
+                for digit in digits.iter_mut().rev() {
+                    *digit += 1;
+                    if *digit < radix {
+                        break;
+                    }
+                    *digit = 0;
+                }
+            }
+        }
+        Coverage::Sampled { samples, seed } => {
+            let mut rng = SplitMix64(seed);
+            for _ in 0..samples {
+                payload.clear();
+                payload.extend((0..payload_length).map(|_| payload_symbols[rng.below(radix)]));
+                payloads += u64::from(measure(&payload, &mut classes));
+            }
+        }
+    }
+
+    Report {
+        system_name: system.name(),
+        payload_length,
+        coverage,
+        payloads,
+        classes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::*;
+    use crate::{
+        MOD_11_10, MOD_11_2, MOD_1271_36, MOD_27_26, MOD_37_2, MOD_37_36, MOD_661_26, MOD_97_10,
+        MOD_97_10_ALPHANUMERIC,
+    };
+    use alloc::vec;
+
+    fn assert_detects_all(report: &Report, class: ErrorClass) {
+        let class_report = report.class(class);
+        assert!(
+            class_report.trials > 0,
+            "{}: no {} trials",
+            report.system_name,
+            class
+        );
+        assert_eq!(
+            0,
+            class_report.undetected(),
+            "{}: undetected {} errors\n{}",
+            report.system_name,
+            class,
+            report
+        );
+    }
+
+    #[test]
+    fn variants_of_each_class() {
+        let symbols = Symbols {
+            payload: Vec::from(&b"0123456789"[..]),
+            check: Vec::from(&b"0123456789X"[..]),
+            check_start: 4,
+        };
+        let mut string = Vec::from(&b"15500"[..]);
+        let variants = |class, string: &mut Vec<u8>| {
+            let mut variants = Vec::new();
+            for_each_variant(class, string, &symbols, |v| variants.push(Vec::from(v)));
+            variants
+        };
+
+        let substitutions = variants(ErrorClass::SingleSubstitution, &mut string);
+        assert_eq!(4 * 9 + 10, substitutions.len());
+        assert!(substitutions.contains(&Vec::from(&b"1550X"[..])));
+        assert_eq!(
+            vec![Vec::from(&b"51500"[..]), Vec::from(&b"15050"[..])],
+            variants(ErrorClass::AdjacentTransposition, &mut string)
+        );
+        assert_eq!(
+            vec![
+                Vec::from(&b"55100"[..]),
+                Vec::from(&b"10550"[..]),
+                Vec::from(&b"15005"[..])
+            ],
+            variants(ErrorClass::JumpTransposition, &mut string)
+        );
+        let twins = variants(ErrorClass::Twin, &mut string);
+        assert_eq!(18, twins.len());
+        assert!(twins.contains(&Vec::from(&b"17700"[..])));
+        assert!(!twins.contains(&Vec::from(&b"155XX"[..])));
+        assert!(variants(ErrorClass::JumpTwin, &mut string).is_empty());
+        assert_eq!(
+            vec![Vec::from(&b"50500"[..]), Vec::from(&b"15150"[..])],
+            variants(ErrorClass::Phonetic, &mut string)
+        );
+        assert_eq!(Vec::from(&b"15500"[..]), string);
+
+        let mut string = Vec::from(&b"1212"[..]);
+        let jump_twins = variants(ErrorClass::JumpTwin, &mut string);
+        assert_eq!(18, jump_twins.len());
+        assert!(!jump_twins.contains(&Vec::from(&b"3212"[..])));
+        assert!(jump_twins.contains(&Vec::from(&b"3232"[..])));
+    }
+
+    #[test]
+    fn exhaustive_counts() {
+        let report = analyze(&MOD_11_2, 3, Coverage::Exhaustive);
+        assert_eq!(1000, report.payloads);
+        // Three payload positions with nine alternatives each, and one check position with ten.
+        assert_eq!(
+            1000 * (3 * 9 + 10),
+            report.class(ErrorClass::SingleSubstitution).trials
+        );
+        assert_eq!(
+            None,
+            analyze(&MOD_27_26, 1, Coverage::Exhaustive).classes[5].rate()
+        );
+    }
+
+    #[test]
+    fn sampling_is_deterministic() {
+        let coverage = Coverage::Sampled {
+            samples: 200,
+            seed: 7064,
+        };
+        let report = analyze(&MOD_37_36, 8, coverage);
+        assert_eq!(200, report.payloads);
+        assert_eq!(report, analyze(&MOD_37_36, 8, coverage));
+    }
+
+    #[test]
+    fn all_systems_detect_all_single_substitutions() {
+        let reports = [
+            analyze(&MOD_11_2, 4, Coverage::Exhaustive),
+            analyze(&MOD_37_2, 2, Coverage::Exhaustive),
+            analyze(&MOD_97_10, 4, Coverage::Exhaustive),
+            analyze(&MOD_661_26, 2, Coverage::Exhaustive),
+            analyze(&MOD_1271_36, 2, Coverage::Exhaustive),
+            analyze(&MOD_11_10, 4, Coverage::Exhaustive),
+            analyze(&MOD_27_26, 2, Coverage::Exhaustive),
+            analyze(&MOD_37_36, 2, Coverage::Exhaustive),
+        ];
+        for report in &reports {
+            assert_detects_all(report, ErrorClass::SingleSubstitution);
+        }
+    }
+
+    #[test]
+    fn pure_systems_detect_all_transpositions() {
+        let coverage = Coverage::Sampled {
+            samples: 500,
+            seed: 1,
+        };
+        let reports = [
+            analyze(&MOD_11_2, 4, Coverage::Exhaustive),
+            analyze(&MOD_11_2, 9, coverage),
+            analyze(&MOD_37_2, 2, Coverage::Exhaustive),
+            analyze(&MOD_37_2, 9, coverage),
+            analyze(&MOD_97_10, 4, Coverage::Exhaustive),
+            analyze(&MOD_97_10, 12, coverage),
+            analyze(&MOD_661_26, 2, Coverage::Exhaustive),
+            analyze(&MOD_661_26, 9, coverage),
+            analyze(&MOD_1271_36, 2, Coverage::Exhaustive),
+            analyze(&MOD_1271_36, 9, coverage),
+        ];
+        for report in &reports {
+            assert_detects_all(report, ErrorClass::AdjacentTransposition);
+            assert_detects_all(report, ErrorClass::JumpTransposition);
+        }
+    }
+
+    #[test]
+    fn pure_mod_97_10_detects_all_tabulated_errors() {
+        let coverage = Coverage::Sampled {
+            samples: 500,
+            seed: 1,
+        };
+        for report in [
+            analyze(&MOD_97_10, 4, Coverage::Exhaustive),
+            analyze(&MOD_97_10, 12, coverage),
+        ] {
+            assert_detects_all(&report, ErrorClass::Twin);
+            assert_detects_all(&report, ErrorClass::JumpTwin);
+            assert_detects_all(&report, ErrorClass::Phonetic);
+        }
+    }
+
+    #[test]
+    fn hybrid_mod_11_10_detection_rates() {
+        // The proportion of undetected errors of each class, which does not depend on the length.
+        let undetected = [
+            (ErrorClass::AdjacentTransposition, 1, 45),
+            (ErrorClass::JumpTransposition, 7, 75),
+            (ErrorClass::Twin, 1, 15),
+            (ErrorClass::JumpTwin, 4, 75),
+            (ErrorClass::Phonetic, 1, 8),
+        ];
+        for length in [3, 5] {
+            let report = analyze(&MOD_11_10, length, Coverage::Exhaustive);
+            assert_detects_all(&report, ErrorClass::SingleSubstitution);
+            for (class, numerator, denominator) in undetected {
+                let class_report = report.class(class);
+                assert_eq!(
+                    class_report.trials * numerator,
+                    class_report.undetected() * denominator,
+                    "{}",
+                    report
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sampled_detection_of_longer_payloads() {
+        let coverage = Coverage::Sampled {
+            samples: 300,
+            seed: 42,
+        };
+        for report in [
+            analyze(&MOD_11_10, 10, coverage),
+            analyze(&MOD_37_36, 10, coverage),
+        ] {
+            assert_eq!(300, report.payloads);
+            assert_detects_all(&report, ErrorClass::SingleSubstitution);
+        }
+
+        // Letters expand to two digits, so a substitution can change the length of the digit
+        // string, and the guarantee of The Standard no longer holds.
+        let report = analyze(&MOD_97_10_ALPHANUMERIC, 10, coverage);
+        let rate = report.class(ErrorClass::SingleSubstitution).rate().unwrap();
+        assert!(0.99 < rate && rate < 1.0, "{}", report);
+    }
+}
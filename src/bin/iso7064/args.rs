@@ -0,0 +1,457 @@
+//! Command-line argument parsing.
+
+use std::fmt;
+
+use iso_iec_7064::StandardSystem;
+
+/// The operation to perform on each input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Check inputs that already have their check character(s) appended.
+    Validate,
+    /// Print the check character(s) for payloads.
+    Compute,
+    /// Print payloads with their check character(s) appended.
+    Append,
+    /// Show the computation of the check character(s) for payloads step by step.
+    Explain,
+    /// Validate one column of CSV or TSV files, reporting failing rows and totals.
+    Batch,
+}
+
+impl Command {
+    fn from_name(name: &str) -> Option<Command> {
+        match name {
+            "validate" => Some(Command::Validate),
+            "compute" => Some(Command::Compute),
+            "append" => Some(Command::Append),
+            "explain" => Some(Command::Explain),
+            "batch" => Some(Command::Batch),
+            _ => None,
+        }
+    }
+}
+
+/// How results are written to standard output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable lines.
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// Comma-separated values, with a header row.
+    Csv,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// The column of a CSV or TSV file to validate in batch mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Zero-based index (given one-based on the command line).
+    Index(usize),
+    /// Name in the header row.
+    Name(String),
+}
+
+impl Column {
+    /// Numbers select a column by (one-based) position, anything else by name.
+    fn parse(value: &str) -> Option<Column> {
+        if value.is_empty() {
+            None
+        } else if value.bytes().all(|c| c.is_ascii_digit()) {
+            match value.parse::<usize>() {
+                Ok(index) if index > 0 => Some(Column::Index(index - 1)),
+                _ => None,
+            }
+        } else {
+            Some(Column::Name(value.to_string()))
+        }
+    }
+}
+
+/// Parse a field delimiter: a single character, or "tab".
+fn parse_delimiter(value: &str) -> Option<char> {
+    match value {
+        "tab" | "\\t" => Some('\t'),
+        _ => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c != '"' && c != '\n' && c != '\r' => Some(c),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// A parsed command line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Args {
+    pub command: Command,
+    pub system: StandardSystem,
+    pub format: Format,
+    pub lenient: bool,
+    /// Inputs given directly on the command line.
+    pub inputs: Vec<String>,
+    /// Files to read inputs from, one per line. "-" is standard input. In batch mode, the CSV or
+    /// TSV files to validate.
+    pub files: Vec<String>,
+    /// In batch mode, the column to validate.
+    pub column: Option<Column>,
+    /// In batch mode, the field delimiter, if not determined from the file name.
+    pub delimiter: Option<char>,
+    /// In batch mode, whether the first row of each file is a header.
+    pub header: bool,
+}
+
+/// What the command line asks for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Invocation {
+    Run(Args),
+    Help,
+    Version,
+}
+
+/// The reason a command line was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UsageError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownOption(String),
+    MissingValue(&'static str),
+    InvalidValue { option: &'static str, value: String },
+    MissingSystem,
+    MissingColumn,
+    NamedColumnWithoutHeader,
+}
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UsageError::MissingCommand => f.write_str("no command given"),
+            UsageError::UnknownCommand(command) => write!(f, "unknown command {:?}", command),
+            UsageError::UnknownOption(option) => write!(f, "unknown option {:?}", option),
+            UsageError::MissingValue(option) => write!(f, "option {} requires a value", option),
+            UsageError::InvalidValue { option, value } => {
+                write!(f, "invalid value {:?} for option {}", value, option)
+            }
+            UsageError::MissingSystem => f.write_str("no system given (use --system)"),
+            UsageError::MissingColumn => f.write_str("no column given (use --column)"),
+            UsageError::NamedColumnWithoutHeader => {
+                f.write_str("a column can only be selected by name from a header row")
+            }
+        }
+    }
+}
+
+pub const USAGE: &str = "\
+Usage: iso7064 <COMMAND> --system <SYSTEM> [OPTIONS] [INPUT]...
+       iso7064 batch --system <SYSTEM> --column <COLUMN> [OPTIONS] [FILE]...
+
+Commands:
+  validate  Check inputs that already have their check character(s) appended
+  compute   Print the check character(s) for payloads
+  append    Print payloads with their check character(s) appended
+  explain   Show the computation of the check character(s) for payloads
+  batch     Validate one column of CSV or TSV files, reporting failing rows and totals
+
+Options:
+  -s, --system <SYSTEM>     Designation (1 to 8) or name (such as \"MOD 97-10\") of the system
+  -f, --file <PATH>         Read inputs from a file, one per line (\"-\" for standard input);
+                            may be repeated
+  -o, --format <FORMAT>     Output format: text (default), json or csv
+  -l, --lenient             Fold case, and ignore spaces, hyphens and dots in inputs
+  -c, --column <COLUMN>     Batch: the column to validate, by one-based number or header name
+  -d, --delimiter <CHAR>    Batch: the field delimiter (default \"tab\" for .tsv files, else \",\")
+      --no-header           Batch: the files have no header row
+  -h, --help                Print this help
+  -V, --version             Print the version
+
+Inputs are read from standard input when none are given as arguments or files, and where \"-\"
+is given as an argument (use \"-- -\" for a literal \"-\" input). In batch mode, failing rows
+are reported with their reason, followed by the numbers of valid, invalid (wrong check
+characters) and malformed rows.
+
+Exit status: 0 if every input succeeded, 1 if any input failed, 2 on usage or I/O errors.
+";
+
+/// Split "--name=value" into its parts.
+fn split_long(arg: &str) -> (&str, Option<&str>) {
+    match arg.split_once('=') {
+        Some((name, value)) if arg.starts_with("--") => (name, Some(value)),
+        _ => (arg, None),
+    }
+}
+
+/// Parse the command line, excluding the program name.
+pub fn parse<I>(args: I) -> Result<Invocation, UsageError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let mut command = None;
+    let mut system = None;
+    let mut format = Format::Text;
+    let mut lenient = false;
+    let mut inputs = Vec::new();
+    let mut files = Vec::new();
+    let mut column = None;
+    let mut delimiter = None;
+    let mut header = true;
+    let mut options_done = false;
+
+    while let Some(arg) = args.next() {
+        if options_done || !arg.starts_with('-') || arg == "-" {
+            if command.is_none() {
+                command = Some(Command::from_name(&arg).ok_or(UsageError::UnknownCommand(arg))?);
+            } else if arg == "-" && !options_done {
+                // A bare "-" reads from standard input, as with "--file -".
+                files.push(arg);
+            } else {
+                inputs.push(arg);
+            }
+            continue;
+        }
+
+        let (name, inline) = split_long(&arg);
+        let mut value = |option: &'static str| match inline {
+            Some(value) => Ok(value.to_string()),
+            None => args.next().ok_or(UsageError::MissingValue(option)),
+        };
+        match name {
+            "--" => options_done = true,
+            "-h" | "--help" => return Ok(Invocation::Help),
+            "-V" | "--version" => return Ok(Invocation::Version),
+            "-l" | "--lenient" => lenient = true,
+            "-s" | "--system" => {
+                let value = value("--system")?;
+                system = Some(value.parse().map_err(|_| UsageError::InvalidValue {
+                    option: "--system",
+                    value,
+                })?);
+            }
+            "-o" | "--format" => {
+                let value = value("--format")?;
+                format = Format::from_name(&value).ok_or(UsageError::InvalidValue {
+                    option: "--format",
+                    value,
+                })?;
+            }
+            "-f" | "--file" => files.push(value("--file")?),
+            "-c" | "--column" => {
+                let value = value("--column")?;
+                column = Some(Column::parse(&value).ok_or(UsageError::InvalidValue {
+                    option: "--column",
+                    value,
+                })?);
+            }
+            "-d" | "--delimiter" => {
+                let value = value("--delimiter")?;
+                delimiter = Some(parse_delimiter(&value).ok_or(UsageError::InvalidValue {
+                    option: "--delimiter",
+                    value,
+                })?);
+            }
+            "--no-header" => header = false,
+            _ => return Err(UsageError::UnknownOption(arg)),
+        }
+    }
+
+    let command = command.ok_or(UsageError::MissingCommand)?;
+    let system = system.ok_or(UsageError::MissingSystem)?;
+    if command == Command::Batch {
+        match column {
+            None => return Err(UsageError::MissingColumn),
+            Some(Column::Name(_)) if !header => return Err(UsageError::NamedColumnWithoutHeader),
+            _ => {}
+        }
+        // Arguments name the files to validate.
+        files.append(&mut inputs);
+    }
+
+    Ok(Invocation::Run(Args {
+        command,
+        system,
+        format,
+        lenient,
+        inputs,
+        files,
+        column,
+        delimiter,
+        header,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Invocation, UsageError> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parse_full_command_line() {
+        assert_eq!(
+            Ok(Invocation::Run(Args {
+                command: Command::Validate,
+                system: StandardSystem::Mod97_10,
+                format: Format::Json,
+                lenient: true,
+                inputs: vec!["79444".into(), "--x".into(), "-".into()],
+                files: vec!["-".into(), "ids.txt".into()],
+                column: None,
+                delimiter: None,
+                header: true,
+            })),
+            parse_strs(&[
+                "validate",
+                "--system=MOD 97-10",
+                "79444",
+                "-o",
+                "json",
+                "-l",
+                "-",
+                "--file",
+                "ids.txt",
+                "--",
+                "--x",
+                "-",
+            ])
+        );
+    }
+
+    #[test]
+    fn bare_dash_reads_standard_input() {
+        match parse_strs(&["validate", "-s", "3", "-", "79444"]) {
+            Ok(Invocation::Run(args)) => {
+                assert_eq!(vec!["79444".to_string()], args.inputs);
+                assert_eq!(vec!["-".to_string()], args.files);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_strs(&["batch", "-s", "3", "-c", "1", "-", "a.csv"]) {
+            Ok(Invocation::Run(args)) => {
+                assert!(args.inputs.is_empty());
+                assert_eq!(vec!["-".to_string(), "a.csv".to_string()], args.files);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn system_by_designation() {
+        match parse_strs(&["compute", "-s", "6", "0794"]) {
+            Ok(Invocation::Run(args)) => {
+                assert_eq!(StandardSystem::Mod11_10, args.system);
+                assert_eq!(Format::Text, args.format);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn batch_options() {
+        assert_eq!(
+            Ok(Invocation::Run(Args {
+                command: Command::Batch,
+                system: StandardSystem::Mod11_2,
+                format: Format::Text,
+                lenient: false,
+                inputs: vec![],
+                files: vec!["a.csv".into(), "b.tsv".into()],
+                column: Some(Column::Index(1)),
+                delimiter: Some('\t'),
+                header: false,
+            })),
+            parse_strs(&[
+                "batch",
+                "-s",
+                "1",
+                "-c",
+                "2",
+                "--delimiter=tab",
+                "--no-header",
+                "a.csv",
+                "b.tsv"
+            ])
+        );
+        match parse_strs(&["batch", "-s", "1", "--column", "LEI code", "-d", ";"]) {
+            Ok(Invocation::Run(args)) => {
+                assert_eq!(Some(Column::Name("LEI code".into())), args.column);
+                assert_eq!(Some(';'), args.delimiter);
+                assert!(args.files.is_empty());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(
+            Err(UsageError::MissingColumn),
+            parse_strs(&["batch", "-s", "1"])
+        );
+        assert_eq!(
+            Err(UsageError::NamedColumnWithoutHeader),
+            parse_strs(&["batch", "-s", "1", "-c", "id", "--no-header"])
+        );
+        assert_eq!(
+            Err(UsageError::InvalidValue {
+                option: "--column",
+                value: "0".into()
+            }),
+            parse_strs(&["batch", "-s", "1", "-c", "0"])
+        );
+        assert_eq!(
+            Err(UsageError::InvalidValue {
+                option: "--delimiter",
+                value: "::".into()
+            }),
+            parse_strs(&["batch", "-s", "1", "-c", "1", "-d", "::"])
+        );
+    }
+
+    #[test]
+    fn help_and_version() {
+        assert_eq!(Ok(Invocation::Help), parse_strs(&["validate", "--help"]));
+        assert_eq!(Ok(Invocation::Version), parse_strs(&["-V"]));
+    }
+
+    #[test]
+    fn report_usage_errors() {
+        assert_eq!(Err(UsageError::MissingCommand), parse_strs(&["-s", "1"]));
+        assert_eq!(
+            Err(UsageError::UnknownCommand("check".into())),
+            parse_strs(&["check"])
+        );
+        assert_eq!(Err(UsageError::MissingSystem), parse_strs(&["validate"]));
+        assert_eq!(
+            Err(UsageError::MissingValue("--system")),
+            parse_strs(&["validate", "-s"])
+        );
+        assert_eq!(
+            Err(UsageError::InvalidValue {
+                option: "--system",
+                value: "9".into()
+            }),
+            parse_strs(&["validate", "-s", "9"])
+        );
+        assert_eq!(
+            Err(UsageError::InvalidValue {
+                option: "--format",
+                value: "xml".into()
+            }),
+            parse_strs(&["validate", "-s", "1", "--format=xml"])
+        );
+        assert_eq!(
+            Err(UsageError::UnknownOption("--verbose".into())),
+            parse_strs(&["validate", "--verbose"])
+        );
+    }
+}
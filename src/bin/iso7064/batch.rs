@@ -0,0 +1,497 @@
+//! Batch validation of one column of CSV or TSV files.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::mem;
+
+use iso_iec_7064::Error;
+
+use crate::args::{Args, Column, Format};
+use crate::output::{error_kind, write_csv_row, write_json_error, write_json_string};
+
+/// Reads the records of a CSV or TSV file. Fields may be quoted with '"', in which case they may
+/// contain the delimiter, line breaks, and '"' doubled.
+pub struct Records<R> {
+    reader: R,
+    delimiter: char,
+    line: usize,
+    buffer: String,
+}
+
+impl<R: BufRead> Records<R> {
+    pub fn new(reader: R, delimiter: char) -> Records<R> {
+        Records {
+            reader,
+            delimiter,
+            line: 0,
+            buffer: String::new(),
+        }
+    }
+
+    /// Read the next line into the buffer, without its line ending. Returns false at the end of
+    /// the input.
+    fn read_line(&mut self) -> io::Result<bool> {
+        self.buffer.clear();
+        if self.reader.read_line(&mut self.buffer)? == 0 {
+            return Ok(false);
+        }
+        self.line += 1;
+        let length = self.buffer.trim_end_matches(['\n', '\r']).len();
+        self.buffer.truncate(length);
+        Ok(true)
+    }
+
+    /// Read the next record, skipping blank lines. Returns the (one-based) line number where the
+    /// record starts, and its fields.
+    pub fn next_record(&mut self) -> io::Result<Option<(usize, Vec<String>)>> {
+        loop {
+            if !self.read_line()? {
+                return Ok(None);
+            }
+            if !self.buffer.is_empty() {
+                break;
+            }
+        }
+
+        let start = self.line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        loop {
+            let mut chars = self.buffer.chars().peekable();
+            while let Some(c) = chars.next() {
+                if quoted {
+                    if c != '"' {
+                        field.push(c);
+                    } else if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        quoted = false;
+                    }
+                } else if c == '"' && field.is_empty() {
+                    quoted = true;
+                } else if c == self.delimiter {
+                    fields.push(mem::take(&mut field));
+                } else {
+                    field.push(c);
+                }
+            }
+            // A quoted field continues on the next line, unless the input ends first.
+            if !quoted || !self.read_line()? {
+                break;
+            }
+            field.push('\n');
+        }
+        fields.push(field);
+
+        Ok(Some((start, fields)))
+    }
+}
+
+/// The delimiter for a file: the one given on the command line, or a tab for files named ".tsv"
+/// or ".tab", or a comma.
+pub fn delimiter_for(path: &str, delimiter: Option<char>) -> char {
+    delimiter.unwrap_or_else(|| {
+        let path = path.to_ascii_lowercase();
+        if path.ends_with(".tsv") || path.ends_with(".tab") {
+            '\t'
+        } else {
+            ','
+        }
+    })
+}
+
+/// Find the index of the column in a header row, by exact name or, failing that, ignoring case.
+pub fn column_index(header: &[String], name: &str) -> Option<usize> {
+    header
+        .iter()
+        .position(|field| field.trim() == name)
+        .or_else(|| {
+            header
+                .iter()
+                .position(|field| field.trim().eq_ignore_ascii_case(name))
+        })
+}
+
+/// The reason a row failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The row does not have the selected column.
+    MissingColumn { fields: usize },
+    /// The value in the column was rejected by the system.
+    Rejected(Error),
+}
+
+impl Failure {
+    /// Invalid rows are well-formed but have the wrong check character(s); all other failures are
+    /// malformed rows.
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, Failure::Rejected(Error::CheckMismatch { .. }))
+    }
+
+    pub fn status(&self) -> &'static str {
+        if self.is_invalid() {
+            "invalid"
+        } else {
+            "malformed"
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Failure::MissingColumn { .. } => "missing_column",
+            Failure::Rejected(error) => error_kind(error),
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Failure::MissingColumn { fields } => {
+                format!("row has only {} field(s)", fields)
+            }
+            Failure::Rejected(error) => error.to_string(),
+        }
+    }
+}
+
+/// The numbers of rows by outcome.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub valid: usize,
+    pub invalid: usize,
+    pub malformed: usize,
+}
+
+impl Totals {
+    pub fn failures(&self) -> usize {
+        self.invalid + self.malformed
+    }
+
+    fn count(&mut self, result: &Result<(), Failure>) {
+        match result {
+            Ok(()) => self.valid += 1,
+            Err(failure) if failure.is_invalid() => self.invalid += 1,
+            Err(_) => self.malformed += 1,
+        }
+    }
+}
+
+/// Writes the failing rows and the totals in the selected format.
+pub struct BatchReporter<W: Write> {
+    out: W,
+    format: Format,
+}
+
+impl<W: Write> BatchReporter<W> {
+    pub fn new(out: W, format: Format) -> BatchReporter<W> {
+        BatchReporter { out, format }
+    }
+
+    pub fn header(&mut self) -> io::Result<()> {
+        if self.format == Format::Csv {
+            write_csv_row(
+                &mut self.out,
+                &["file", "line", "value", "status", "error", "message"],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn failure(
+        &mut self,
+        file: &str,
+        line: usize,
+        value: Option<&str>,
+        failure: &Failure,
+    ) -> io::Result<()> {
+        let out = &mut self.out;
+        match self.format {
+            Format::Text => {
+                write!(out, "{}:{}: ", file, line)?;
+                if let Some(value) = value {
+                    write!(out, "{:?} ", value)?;
+                }
+                writeln!(out, "{} ({})", failure.status(), failure.message())
+            }
+            Format::Json => {
+                out.write_all(b"{\"file\":")?;
+                write_json_string(out, file)?;
+                write!(out, ",\"line\":{},\"value\":", line)?;
+                match value {
+                    Some(value) => write_json_string(out, value)?,
+                    None => out.write_all(b"null")?,
+                }
+                write!(out, ",\"status\":\"{}\"", failure.status())?;
+                match failure {
+                    Failure::MissingColumn { fields } => {
+                        write!(out, ",\"error\":\"{}\",\"message\":", failure.kind())?;
+                        write_json_string(out, &failure.message())?;
+                        write!(out, ",\"fields\":{}", fields)?;
+                    }
+                    Failure::Rejected(error) => write_json_error(out, error)?,
+                }
+                out.write_all(b"}\n")
+            }
+            Format::Csv => write_csv_row(
+                out,
+                &[
+                    file,
+                    &line.to_string(),
+                    value.unwrap_or_default(),
+                    failure.status(),
+                    failure.kind(),
+                    &failure.message(),
+                ],
+            ),
+        }
+    }
+
+    pub fn totals(&mut self, totals: &Totals) -> io::Result<()> {
+        let Totals {
+            valid,
+            invalid,
+            malformed,
+        } = totals;
+        match self.format {
+            Format::Text => writeln!(
+                self.out,
+                "valid: {}, invalid: {}, malformed: {}",
+                valid, invalid, malformed
+            ),
+            Format::Json => writeln!(
+                self.out,
+                "{{\"totals\":{{\"valid\":{},\"invalid\":{},\"malformed\":{}}}}}",
+                valid, invalid, malformed
+            ),
+            // Keep standard output a well-formed CSV file of the failing rows.
+            Format::Csv => {
+                self.out.flush()?;
+                eprintln!(
+                    "valid: {}, invalid: {}, malformed: {}",
+                    valid, invalid, malformed
+                );
+                Ok(())
+            }
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+/// Validate the selected column of every row of one file.
+pub fn validate_records<R, W>(
+    args: &Args,
+    file: &str,
+    records: &mut Records<R>,
+    reporter: &mut BatchReporter<W>,
+    totals: &mut Totals,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    let column = args.column.as_ref().expect("batch mode requires a column");
+    let index = if args.header {
+        let header = records.next_record()?.map(|(_, fields)| fields);
+        match column {
+            Column::Index(index) => *index,
+            Column::Name(name) => header
+                .as_deref()
+                .and_then(|header| column_index(header, name))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}: no column named {:?} in the header row", file, name),
+                    )
+                })?,
+        }
+    } else {
+        match column {
+            Column::Index(index) => *index,
+            Column::Name(_) => unreachable!("rejected when parsing arguments"),
+        }
+    };
+
+    while let Some((line, fields)) = records.next_record()? {
+        let value = fields.get(index).map(|value| value.trim());
+        let result = match value {
+            Some(value) => crate::process(args, value)
+                .map(|_| ())
+                .map_err(Failure::Rejected),
+            None => Err(Failure::MissingColumn {
+                fields: fields.len(),
+            }),
+        };
+        totals.count(&result);
+        if let Err(failure) = result {
+            reporter.failure(file, line, value, &failure)?;
+        }
+    }
+    Ok(())
+}
+
+/// Validate every file, or standard input if none are given. Returns the number of failing rows.
+pub fn run(args: &Args) -> io::Result<usize> {
+    let stdout = io::stdout();
+    let mut reporter = BatchReporter::new(stdout.lock(), args.format);
+    reporter.header()?;
+
+    let mut totals = Totals::default();
+    let stdin = [String::from("-")];
+    let files = if args.files.is_empty() {
+        &stdin[..]
+    } else {
+        &args.files[..]
+    };
+    for path in files {
+        let delimiter = delimiter_for(path, args.delimiter);
+        if path == "-" {
+            let mut records = Records::new(io::stdin().lock(), delimiter);
+            validate_records(args, "<stdin>", &mut records, &mut reporter, &mut totals)?;
+        } else {
+            let file = File::open(path)
+                .map_err(|error| io::Error::new(error.kind(), format!("{}: {}", path, error)))?;
+            let mut records = Records::new(BufReader::new(file), delimiter);
+            validate_records(args, path, &mut records, &mut reporter, &mut totals)?;
+        }
+    }
+
+    reporter.totals(&totals)?;
+    reporter.flush()?;
+    Ok(totals.failures())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::Command;
+    use iso_iec_7064::StandardSystem;
+
+    fn read_all(input: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+        let mut records = Records::new(input.as_bytes(), delimiter);
+        let mut all = Vec::new();
+        while let Some(record) = records.next_record().unwrap() {
+            all.push(record);
+        }
+        all
+    }
+
+    #[test]
+    fn read_quoted_fields() {
+        let input = "id,name\r\n1,\"Smith, \"\"J\"\"\"\n\n2,\"two\nlines\",x\n3\n";
+        assert_eq!(
+            vec![
+                (1, vec!["id".to_string(), "name".into()]),
+                (2, vec!["1".into(), "Smith, \"J\"".into()]),
+                (4, vec!["2".into(), "two\nlines".into(), "x".into()]),
+                (6, vec!["3".into()]),
+            ],
+            read_all(input, ',')
+        );
+        assert_eq!(
+            vec![(1, vec!["a,b".to_string(), "c".into()])],
+            read_all("a,b\tc", '\t')
+        );
+    }
+
+    #[test]
+    fn choose_delimiter_and_column() {
+        assert_eq!('\t', delimiter_for("export.TSV", None));
+        assert_eq!(',', delimiter_for("export.csv", None));
+        assert_eq!(';', delimiter_for("export.tsv", Some(';')));
+
+        let header: Vec<String> = vec!["id".into(), " IBAN ".into(), "iban".into()];
+        assert_eq!(Some(2), column_index(&header, "iban"));
+        assert_eq!(Some(1), column_index(&header, "IBAN"));
+        assert_eq!(None, column_index(&header, "lei"));
+    }
+
+    fn batch_args(column: Column, format: Format) -> Args {
+        Args {
+            command: Command::Batch,
+            system: StandardSystem::Mod97_10,
+            format,
+            lenient: false,
+            inputs: Vec::new(),
+            files: Vec::new(),
+            column: Some(column),
+            delimiter: None,
+            header: true,
+        }
+    }
+
+    fn validate_str(args: &Args, input: &str) -> io::Result<(String, Totals)> {
+        let mut records = Records::new(input.as_bytes(), ',');
+        let mut reporter = BatchReporter::new(Vec::new(), args.format);
+        let mut totals = Totals::default();
+        reporter.header()?;
+        validate_records(args, "data.csv", &mut records, &mut reporter, &mut totals)?;
+        reporter.totals(&totals)?;
+        Ok((String::from_utf8(reporter.out).unwrap(), totals))
+    }
+
+    const DATA: &str = "name,reference\na,79444\nb,79445\nc,79A44\nd\n";
+
+    #[test]
+    fn report_failing_rows_and_totals() {
+        let (text, totals) = validate_str(
+            &batch_args(Column::Name("Reference".into()), Format::Text),
+            DATA,
+        )
+        .unwrap();
+        assert_eq!(
+            Totals {
+                valid: 1,
+                invalid: 1,
+                malformed: 2
+            },
+            totals
+        );
+        assert_eq!(
+            concat!(
+                "data.csv:3: \"79445\" invalid (check character(s) \"45\" do not match expected \"44\")\n",
+                "data.csv:4: \"79A44\" malformed (illegal character 'A' at position 2)\n",
+                "data.csv:5: malformed (row has only 1 field(s))\n",
+                "valid: 1, invalid: 1, malformed: 2\n"
+            ),
+            text
+        );
+
+        let (json, _) = validate_str(&batch_args(Column::Index(1), Format::Json), DATA).unwrap();
+        let lines: Vec<&str> = json.lines().collect();
+        assert_eq!(
+            concat!(
+                "{\"file\":\"data.csv\",\"line\":4,\"value\":\"79A44\",\"status\":\"malformed\",",
+                "\"error\":\"illegal_character\",\"message\":\"illegal character 'A' at position 2\",",
+                "\"position\":2}"
+            ),
+            lines[1]
+        );
+        assert_eq!(
+            concat!(
+                "{\"file\":\"data.csv\",\"line\":5,\"value\":null,\"status\":\"malformed\",",
+                "\"error\":\"missing_column\",\"message\":\"row has only 1 field(s)\",\"fields\":1}"
+            ),
+            lines[2]
+        );
+        assert_eq!(
+            "{\"totals\":{\"valid\":1,\"invalid\":1,\"malformed\":2}}",
+            lines[3]
+        );
+
+        let (csv, _) = validate_str(&batch_args(Column::Index(1), Format::Csv), DATA).unwrap();
+        assert!(csv.starts_with(
+            "file,line,value,status,error,message\ndata.csv,3,79445,invalid,check_mismatch,"
+        ));
+    }
+
+    #[test]
+    fn unknown_column_name_is_an_error() {
+        let error =
+            validate_str(&batch_args(Column::Name("lei".into()), Format::Text), DATA).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+}
@@ -0,0 +1,215 @@
+//! Step-by-step computation of check characters, following the recursive methods of The Standard.
+
+use std::fmt;
+
+use iso_iec_7064::{CheckChars, DynSystem, Error, StandardSystem};
+
+/// The parameters of the computation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// A _Pure System_ with modulus M and radix r.
+    Pure { modulus: usize, radix: usize },
+    /// A _Hybrid System_ with modulus M (and M + 1).
+    Hybrid { modulus: usize },
+}
+
+impl Method {
+    pub fn of(system: StandardSystem) -> Method {
+        match system {
+            StandardSystem::Mod11_2 => Method::Pure {
+                modulus: 11,
+                radix: 2,
+            },
+            StandardSystem::Mod37_2 => Method::Pure {
+                modulus: 37,
+                radix: 2,
+            },
+            StandardSystem::Mod97_10 => Method::Pure {
+                modulus: 97,
+                radix: 10,
+            },
+            StandardSystem::Mod661_26 => Method::Pure {
+                modulus: 661,
+                radix: 26,
+            },
+            StandardSystem::Mod1271_36 => Method::Pure {
+                modulus: 1271,
+                radix: 36,
+            },
+            StandardSystem::Mod11_10 => Method::Hybrid { modulus: 10 },
+            StandardSystem::Mod27_26 => Method::Hybrid { modulus: 26 },
+            StandardSystem::Mod37_36 => Method::Hybrid { modulus: 36 },
+        }
+    }
+
+    /// The modulus M.
+    pub fn modulus(self) -> usize {
+        match self {
+            Method::Pure { modulus, .. } | Method::Hybrid { modulus } => modulus,
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Method::Pure { modulus, radix } => {
+                write!(f, "pure system, M = {}, r = {}", modulus, radix)
+            }
+            Method::Hybrid { modulus } => {
+                write!(f, "hybrid system, M = {}, M + 1 = {}", modulus, modulus + 1)
+            }
+        }
+    }
+}
+
+/// One iteration of the computation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// The payload character, or `None` for a check character position, which counts as zero.
+    pub character: Option<u8>,
+    /// The value of the character.
+    pub value: u8,
+    /// For hybrid systems, the sum S computed in this iteration.
+    pub sum: Option<usize>,
+    /// The product P carried into the next iteration.
+    pub product: usize,
+}
+
+/// The complete computation for a payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    pub method: Method,
+    pub steps: Vec<Step>,
+    pub checksum: u16,
+    pub check: CheckChars,
+}
+
+/// Compute the check character(s) for a payload, recording every step.
+pub fn explain(system: StandardSystem, payload: &str) -> Result<Explanation, Error> {
+    let checksum = system.try_checksum_str(payload)?;
+    let check = system
+        .render_check_chars(checksum)
+        .expect("checksum is always representable");
+    let alphabet = system.system_alphabet();
+    let values = payload.bytes().map(|c| {
+        let value = alphabet
+            .char_value(c)
+            .expect("payload was accepted by the system");
+        (Some(c), value)
+    });
+
+    let method = Method::of(system);
+    let mut steps = Vec::with_capacity(payload.len() + 2);
+    match method {
+        Method::Pure { modulus, radix } => {
+            // The check character positions take part in the computation as zeros.
+            let zeros = (0..system.system_check_length()).map(|_| (None, 0));
+            let mut product = 0;
+            for (character, value) in values.chain(zeros) {
+                product = (product * radix + value as usize) % modulus;
+                steps.push(Step {
+                    character,
+                    value,
+                    sum: None,
+                    product,
+                });
+            }
+        }
+        Method::Hybrid { modulus } => {
+            let mut product = modulus;
+            for (character, value) in values {
+                let sum = match (product + value as usize) % modulus {
+                    0 => modulus,
+                    sum => sum,
+                };
+                product = (sum * 2) % (modulus + 1);
+                steps.push(Step {
+                    character,
+                    value,
+                    sum: Some(sum),
+                    product,
+                });
+            }
+        }
+    }
+
+    Ok(Explanation {
+        method,
+        steps,
+        checksum,
+        check,
+    })
+}
+
+impl Explanation {
+    /// The product after the last step.
+    pub fn final_product(&self) -> usize {
+        self.steps.last().map_or(0, |step| step.product)
+    }
+
+    /// The formula deriving the checksum from the final product, with values substituted.
+    pub fn formula(&self) -> String {
+        let modulus = self.method.modulus();
+        format!(
+            "({} + 1 - {}) mod {} = {}",
+            modulus,
+            self.final_product(),
+            modulus,
+            self.checksum
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pure_steps() {
+        let explanation = explain(StandardSystem::Mod11_2, "0794").unwrap();
+        let products: Vec<usize> = explanation.steps.iter().map(|s| s.product).collect();
+        // 0, 7, 14 + 9 = 23 = 1, 2 + 4 = 6, then the check position: 12 = 1.
+        assert_eq!(vec![0, 7, 1, 6, 1], products);
+        assert_eq!(None, explanation.steps[4].character);
+        assert_eq!(0, explanation.checksum);
+        assert_eq!("0", explanation.check.as_str());
+        assert_eq!("(11 + 1 - 1) mod 11 = 0", explanation.formula());
+    }
+
+    #[test]
+    fn hybrid_steps() {
+        let explanation = explain(StandardSystem::Mod11_10, "0794").unwrap();
+        let sums: Vec<Option<usize>> = explanation.steps.iter().map(|s| s.sum).collect();
+        let products: Vec<usize> = explanation.steps.iter().map(|s| s.product).collect();
+        assert_eq!(vec![Some(10), Some(6), Some(10), Some(3)], sums);
+        assert_eq!(vec![9, 1, 9, 6], products);
+        assert_eq!("5", explanation.check.as_str());
+        assert_eq!("(10 + 1 - 6) mod 10 = 5", explanation.formula());
+    }
+
+    #[test]
+    fn steps_agree_with_checksum_for_all_systems() {
+        for system in StandardSystem::ALL {
+            let payload = system.system_alphabet().payload_symbols();
+            let explanation = explain(system, payload).unwrap();
+            let modulus = explanation.method.modulus();
+            assert_eq!(
+                (modulus + 1 - explanation.final_product()) % modulus,
+                explanation.checksum as usize,
+                "{}",
+                system.system_name()
+            );
+            let complete = format!("{}{}", payload, explanation.check);
+            assert!(system.validate_str(&complete), "{}", system.system_name());
+        }
+    }
+
+    #[test]
+    fn report_errors() {
+        assert!(matches!(
+            explain(StandardSystem::Mod97_10, "79A"),
+            Err(Error::IllegalCharacter { position: 2, .. })
+        ));
+    }
+}
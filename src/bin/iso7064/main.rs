@@ -0,0 +1,211 @@
+//! # iso7064
+//!
+//! Command-line tool for validating identifiers and computing check characters with the _Check
+//! Character Systems_ of ISO/IEC 7064. Run `iso7064 --help` for usage.
+
+mod args;
+mod batch;
+mod explain;
+mod output;
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::ExitCode;
+
+use iso_iec_7064::{DynSystem, Error, Normalizer};
+
+use args::{Args, Command, Invocation, USAGE};
+use output::{Outcome, Reporter};
+
+/// Perform the command on one input.
+fn process(args: &Args, input: &str) -> Result<Outcome, Error> {
+    let normalized = args.lenient.then(|| Normalizer::LENIENT.normalize(input));
+    let value = normalized
+        .as_ref()
+        .map_or(input, |normalized| normalized.as_str());
+    let system = args.system;
+
+    let result = match args.command {
+        Command::Validate | Command::Batch => {
+            system.try_validate_str(value).map(|_| Outcome::Valid)
+        }
+        Command::Compute | Command::Append => system.try_checksum_str(value).map(|checksum| {
+            let check = system
+                .render_check_chars(checksum)
+                .expect("checksum is always representable");
+            if args.command == Command::Compute {
+                Outcome::Check(check)
+            } else {
+                Outcome::Completed(format!("{}{}", value, check))
+            }
+        }),
+        Command::Explain => explain::explain(system, value).map(Outcome::Explained),
+    };
+    match &normalized {
+        Some(normalized) => result.map_err(|error| normalized.original_error(error)),
+        None => result,
+    }
+}
+
+/// Process every line of the reader as an input, skipping blank lines. Returns the number of
+/// inputs that failed.
+fn process_lines<R, W>(args: &Args, reader: R, reporter: &mut Reporter<W>) -> io::Result<usize>
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut failures = 0;
+    for line in reader.lines() {
+        let line = line?;
+        let input = line.trim();
+        if input.is_empty() {
+            continue;
+        }
+        let result = process(args, input);
+        failures += usize::from(result.is_err());
+        reporter.record(input, &result)?;
+    }
+    Ok(failures)
+}
+
+/// Run the command on all inputs. Returns the number of inputs that failed.
+fn run(args: &Args) -> io::Result<usize> {
+    if args.command == Command::Batch {
+        return batch::run(args);
+    }
+
+    let stdout = io::stdout();
+    let mut reporter = Reporter::new(stdout.lock(), args.command, args.format, args.system);
+    reporter.header()?;
+
+    let mut failures = 0;
+    for input in &args.inputs {
+        let input = input.trim();
+        let result = process(args, input);
+        failures += usize::from(result.is_err());
+        reporter.record(input, &result)?;
+    }
+    for path in &args.files {
+        failures += if path == "-" {
+            process_lines(args, io::stdin().lock(), &mut reporter)?
+        } else {
+            let file = File::open(path)
+                .map_err(|error| io::Error::new(error.kind(), format!("{}: {}", path, error)))?;
+            process_lines(args, BufReader::new(file), &mut reporter)?
+        };
+    }
+    if args.inputs.is_empty() && args.files.is_empty() {
+        failures += process_lines(args, io::stdin().lock(), &mut reporter)?;
+    }
+
+    reporter.flush()?;
+    Ok(failures)
+}
+
+fn main() -> ExitCode {
+    match args::parse(std::env::args().skip(1)) {
+        Ok(Invocation::Help) => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Ok(Invocation::Version) => {
+            println!("iso7064 {}", env!("CARGO_PKG_VERSION"));
+            ExitCode::SUCCESS
+        }
+        Ok(Invocation::Run(args)) => match run(&args) {
+            Ok(0) => ExitCode::SUCCESS,
+            Ok(_) => ExitCode::from(1),
+            Err(error) => {
+                eprintln!("iso7064: {}", error);
+                ExitCode::from(2)
+            }
+        },
+        Err(error) => {
+            eprintln!("iso7064: {}\nRun 'iso7064 --help' for usage.", error);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iso_iec_7064::StandardSystem;
+
+    fn args(command: Command, lenient: bool) -> Args {
+        Args {
+            command,
+            system: StandardSystem::Mod97_10,
+            format: args::Format::Text,
+            lenient,
+            inputs: Vec::new(),
+            files: Vec::new(),
+            column: None,
+            delimiter: None,
+            header: true,
+        }
+    }
+
+    #[test]
+    fn process_each_command() {
+        assert_eq!(
+            Ok(Outcome::Valid),
+            process(&args(Command::Validate, false), "79444")
+        );
+        assert!(matches!(
+            process(&args(Command::Validate, false), "79445"),
+            Err(Error::CheckMismatch { .. })
+        ));
+        assert!(matches!(
+            process(&args(Command::Compute, false), "794"),
+            Ok(Outcome::Check(check)) if check.as_str() == "44"
+        ));
+        assert_eq!(
+            Ok(Outcome::Completed("79444".into())),
+            process(&args(Command::Append, false), "794")
+        );
+        assert!(matches!(
+            process(&args(Command::Explain, false), "794"),
+            Ok(Outcome::Explained(_))
+        ));
+    }
+
+    #[test]
+    fn lenient_reports_original_positions() {
+        assert_eq!(
+            Ok(Outcome::Valid),
+            process(&args(Command::Validate, true), "794-44")
+        );
+        assert_eq!(
+            Ok(Outcome::Completed("79444".into())),
+            process(&args(Command::Append, true), "7 9 4")
+        );
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 3,
+                byte: b'A'
+            }),
+            process(&args(Command::Validate, true), "7-9A44")
+        );
+    }
+
+    #[test]
+    fn process_lines_counts_failures_and_skips_blank_lines() {
+        let mut reporter = Reporter::new(
+            Vec::new(),
+            Command::Validate,
+            args::Format::Text,
+            StandardSystem::Mod97_10,
+        );
+        let input = "79444\n\n  79445 \r\n79444\n";
+        assert_eq!(
+            1,
+            process_lines(
+                &args(Command::Validate, false),
+                input.as_bytes(),
+                &mut reporter
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! Writing results as text, JSON or CSV.
+
+use std::io::{self, Write};
+
+use iso_iec_7064::{CheckChars, DynSystem, Error, StandardSystem};
+
+use crate::args::{Command, Format};
+use crate::explain::{Explanation, Method};
+
+/// The successful result of processing one input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Valid,
+    Check(CheckChars),
+    Completed(String),
+    Explained(Explanation),
+}
+
+/// A short, stable name for the kind of error, for machine-readable output.
+pub fn error_kind(error: &Error) -> &'static str {
+    match error {
+        Error::IllegalCharacter { .. } => "illegal_character",
+        Error::SupplementaryCharacterInPayload { .. } => "supplementary_character_in_payload",
+        Error::TooShort { .. } => "too_short",
+        Error::StrippedCharacter { .. } => "stripped_character",
+        Error::CheckMismatch { .. } => "check_mismatch",
+        Error::Rejected => "rejected",
+    }
+}
+
+/// Write a string as a JSON string literal.
+pub fn write_json_string<W: Write>(out: &mut W, s: &str) -> io::Result<()> {
+    out.write_all(b"\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_all(b"\\\"")?,
+            '\\' => out.write_all(b"\\\\")?,
+            '\n' => out.write_all(b"\\n")?,
+            '\r' => out.write_all(b"\\r")?,
+            '\t' => out.write_all(b"\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{}", c)?,
+        }
+    }
+    out.write_all(b"\"")
+}
+
+/// Write a row of CSV fields, quoting those that need it.
+pub fn write_csv_row<W: Write>(out: &mut W, fields: &[&str]) -> io::Result<()> {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.write_all(b",")?;
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            write!(out, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            out.write_all(field.as_bytes())?;
+        }
+    }
+    out.write_all(b"\n")
+}
+
+/// Write the fields describing an error into a JSON object that has already been opened.
+pub fn write_json_error<W: Write>(out: &mut W, error: &Error) -> io::Result<()> {
+    write!(out, ",\"error\":\"{}\",\"message\":", error_kind(error))?;
+    write_json_string(out, &error.to_string())?;
+    match error {
+        Error::IllegalCharacter { position, .. }
+        | Error::SupplementaryCharacterInPayload { position, .. }
+        | Error::StrippedCharacter { position, .. } => write!(out, ",\"position\":{}", position),
+        Error::TooShort { minimum, length } => {
+            write!(out, ",\"minimum\":{},\"length\":{}", minimum, length)
+        }
+        Error::CheckMismatch { expected, found } => {
+            out.write_all(b",\"expected\":")?;
+            write_json_string(out, expected.as_str())?;
+            out.write_all(b",\"found\":")?;
+            write_json_string(out, found.as_str())
+        }
+        Error::Rejected => Ok(()),
+    }
+}
+
+/// Writes the result for each input in the selected format.
+pub struct Reporter<W: Write> {
+    out: W,
+    command: Command,
+    format: Format,
+    system: StandardSystem,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(out: W, command: Command, format: Format, system: StandardSystem) -> Reporter<W> {
+        Reporter {
+            out,
+            command,
+            format,
+            system,
+        }
+    }
+
+    /// Write the header row, for formats that have one.
+    pub fn header(&mut self) -> io::Result<()> {
+        if self.format != Format::Csv {
+            return Ok(());
+        }
+        let fields: &[&str] = match self.command {
+            Command::Validate | Command::Batch => &["input", "valid", "error", "message"],
+            Command::Compute => &["input", "check", "error", "message"],
+            Command::Append => &["input", "output", "error", "message"],
+            Command::Explain => &[
+                "input",
+                "step",
+                "character",
+                "value",
+                "sum",
+                "product",
+                "check",
+                "error",
+                "message",
+            ],
+        };
+        write_csv_row(&mut self.out, fields)
+    }
+
+    /// Write the result for one input.
+    pub fn record(&mut self, input: &str, result: &Result<Outcome, Error>) -> io::Result<()> {
+        match self.format {
+            Format::Text => self.text(input, result),
+            Format::Json => self.json(input, result),
+            Format::Csv => self.csv(input, result),
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    fn text(&mut self, input: &str, result: &Result<Outcome, Error>) -> io::Result<()> {
+        match (self.command, result) {
+            (Command::Validate, Ok(_)) => writeln!(self.out, "{}: valid", input),
+            (Command::Validate, Err(error)) => writeln!(self.out, "{}: invalid ({})", input, error),
+            (_, Ok(Outcome::Check(check))) => writeln!(self.out, "{}", check),
+            (_, Ok(Outcome::Completed(completed))) => writeln!(self.out, "{}", completed),
+            (_, Ok(Outcome::Explained(explanation))) => self.text_explanation(input, explanation),
+            (_, Ok(Outcome::Valid)) => Ok(()),
+            // Keep standard output to one result per line for scripts, and report errors aside.
+            (_, Err(error)) => {
+                self.out.flush()?;
+                eprintln!("iso7064: {}: {}", input, error);
+                Ok(())
+            }
+        }
+    }
+
+    fn text_explanation(&mut self, input: &str, explanation: &Explanation) -> io::Result<()> {
+        let out = &mut self.out;
+        writeln!(
+            out,
+            "{:?} with {} ({})",
+            input,
+            self.system.system_name(),
+            explanation.method
+        )?;
+        let hybrid = matches!(explanation.method, Method::Hybrid { .. });
+        if hybrid {
+            writeln!(out, "  step  char  value     S     P")?;
+        } else {
+            writeln!(out, "  step  char  value     P")?;
+        }
+        for (i, step) in explanation.steps.iter().enumerate() {
+            let character = step.character.map_or('-', char::from);
+            write!(out, "  {:>4}  {:>4}  {:>5}", i + 1, character, step.value)?;
+            if let Some(sum) = step.sum {
+                write!(out, "  {:>4}", sum)?;
+            }
+            writeln!(out, "  {:>4}", step.product)?;
+        }
+        writeln!(out, "  checksum = {}", explanation.formula())?;
+        writeln!(out, "  check character(s): {}", explanation.check)?;
+        writeln!(out)
+    }
+
+    fn json(&mut self, input: &str, result: &Result<Outcome, Error>) -> io::Result<()> {
+        let out = &mut self.out;
+        out.write_all(b"{\"input\":")?;
+        write_json_string(out, input)?;
+        match (self.command, result) {
+            (Command::Validate, Ok(_)) => out.write_all(b",\"valid\":true")?,
+            (Command::Validate, Err(error)) => {
+                out.write_all(b",\"valid\":false")?;
+                write_json_error(out, error)?;
+            }
+            (_, Ok(Outcome::Check(check))) => {
+                out.write_all(b",\"check\":")?;
+                write_json_string(out, check.as_str())?;
+            }
+            (_, Ok(Outcome::Completed(completed))) => {
+                out.write_all(b",\"output\":")?;
+                write_json_string(out, completed)?;
+            }
+            (_, Ok(Outcome::Explained(explanation))) => {
+                match explanation.method {
+                    Method::Pure { modulus, radix } => write!(
+                        out,
+                        ",\"method\":\"pure\",\"modulus\":{},\"radix\":{}",
+                        modulus, radix
+                    )?,
+                    Method::Hybrid { modulus } => {
+                        write!(out, ",\"method\":\"hybrid\",\"modulus\":{}", modulus)?
+                    }
+                }
+                out.write_all(b",\"steps\":[")?;
+                for (i, step) in explanation.steps.iter().enumerate() {
+                    if i > 0 {
+                        out.write_all(b",")?;
+                    }
+                    out.write_all(b"{\"character\":")?;
+                    match step.character {
+                        Some(c) => write_json_string(out, &char::from(c).to_string())?,
+                        None => out.write_all(b"null")?,
+                    }
+                    write!(out, ",\"value\":{}", step.value)?;
+                    if let Some(sum) = step.sum {
+                        write!(out, ",\"sum\":{}", sum)?;
+                    }
+                    write!(out, ",\"product\":{}}}", step.product)?;
+                }
+                write!(out, "],\"checksum\":{},\"check\":", explanation.checksum)?;
+                write_json_string(out, explanation.check.as_str())?;
+            }
+            (_, Ok(Outcome::Valid)) => {}
+            (_, Err(error)) => write_json_error(out, error)?,
+        }
+        out.write_all(b"}\n")
+    }
+
+    fn csv(&mut self, input: &str, result: &Result<Outcome, Error>) -> io::Result<()> {
+        let out = &mut self.out;
+        let (kind, message) = match result {
+            Ok(_) => ("", String::new()),
+            Err(error) => (error_kind(error), error.to_string()),
+        };
+        match (self.command, result) {
+            (Command::Validate, _) => {
+                let valid = if result.is_ok() { "true" } else { "false" };
+                write_csv_row(out, &[input, valid, kind, &message])
+            }
+            (Command::Explain, Ok(Outcome::Explained(explanation))) => {
+                for (i, step) in explanation.steps.iter().enumerate() {
+                    let character = step
+                        .character
+                        .map(|c| char::from(c).to_string())
+                        .unwrap_or_default();
+                    let sum = step.sum.map(|sum| sum.to_string()).unwrap_or_default();
+                    write_csv_row(
+                        out,
+                        &[
+                            input,
+                            &(i + 1).to_string(),
+                            &character,
+                            &step.value.to_string(),
+                            &sum,
+                            &step.product.to_string(),
+                            explanation.check.as_str(),
+                            "",
+                            "",
+                        ],
+                    )?;
+                }
+                Ok(())
+            }
+            (Command::Explain, _) => {
+                write_csv_row(out, &[input, "", "", "", "", "", "", kind, &message])
+            }
+            (_, Ok(Outcome::Check(check))) => write_csv_row(out, &[input, check.as_str(), "", ""]),
+            (_, Ok(Outcome::Completed(completed))) => {
+                write_csv_row(out, &[input, completed, "", ""])
+            }
+            (_, _) => write_csv_row(out, &[input, "", kind, &message]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(
+        command: Command,
+        format: Format,
+        records: &[(&str, Result<Outcome, Error>)],
+    ) -> String {
+        let mut reporter = Reporter::new(Vec::new(), command, format, StandardSystem::Mod11_2);
+        reporter.header().unwrap();
+        for (input, result) in records {
+            reporter.record(input, result).unwrap();
+        }
+        String::from_utf8(reporter.out).unwrap()
+    }
+
+    fn mismatch() -> Error {
+        Error::CheckMismatch {
+            expected: CheckChars::from_ascii_bytes(b"0").unwrap(),
+            found: CheckChars::from_ascii_bytes(b"1").unwrap(),
+        }
+    }
+
+    #[test]
+    fn escape_json_and_csv() {
+        let mut out = Vec::new();
+        write_json_string(&mut out, "a\"b\\c\n\u{1}").unwrap();
+        assert_eq!(r#""a\"b\\c\n\u0001""#, String::from_utf8(out).unwrap());
+
+        let mut out = Vec::new();
+        write_csv_row(&mut out, &["plain", "with,comma", "with \"quote\""]).unwrap();
+        assert_eq!(
+            "plain,\"with,comma\",\"with \"\"quote\"\"\"\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn validate_formats() {
+        let records = [("07940", Ok(Outcome::Valid)), ("07941", Err(mismatch()))];
+        assert_eq!(
+            "07940: valid\n07941: invalid (check character(s) \"1\" do not match expected \"0\")\n",
+            render(Command::Validate, Format::Text, &records)
+        );
+        assert_eq!(
+            concat!(
+                "{\"input\":\"07940\",\"valid\":true}\n",
+                "{\"input\":\"07941\",\"valid\":false,\"error\":\"check_mismatch\",",
+                "\"message\":\"check character(s) \\\"1\\\" do not match expected \\\"0\\\"\",",
+                "\"expected\":\"0\",\"found\":\"1\"}\n"
+            ),
+            render(Command::Validate, Format::Json, &records)
+        );
+        assert_eq!(
+            concat!(
+                "input,valid,error,message\n",
+                "07940,true,,\n",
+                "07941,false,check_mismatch,\"check character(s) \"\"1\"\" do not match expected \"\"0\"\"\"\n"
+            ),
+            render(Command::Validate, Format::Csv, &records)
+        );
+    }
+
+    #[test]
+    fn compute_and_append_formats() {
+        let check = CheckChars::from_ascii_bytes(b"0").unwrap();
+        let error = Error::IllegalCharacter {
+            position: 1,
+            byte: b'A',
+        };
+        let records = [("0794", Ok(Outcome::Check(check))), ("0A", Err(error))];
+        assert_eq!("0\n", render(Command::Compute, Format::Text, &records));
+        assert_eq!(
+            concat!(
+                "{\"input\":\"0794\",\"check\":\"0\"}\n",
+                "{\"input\":\"0A\",\"error\":\"illegal_character\",",
+                "\"message\":\"illegal character 'A' at position 1\",\"position\":1}\n"
+            ),
+            render(Command::Compute, Format::Json, &records)
+        );
+        assert_eq!(
+            "input,output,error,message\n0794,07940,,\n",
+            render(
+                Command::Append,
+                Format::Csv,
+                &[("0794", Ok(Outcome::Completed("07940".into())))]
+            )
+        );
+    }
+
+    #[test]
+    fn explain_formats() {
+        let explanation = crate::explain::explain(StandardSystem::Mod11_2, "07").unwrap();
+        let records = [("07", Ok(Outcome::Explained(explanation)))];
+        assert_eq!(
+            concat!(
+                "{\"input\":\"07\",\"method\":\"pure\",\"modulus\":11,\"radix\":2,\"steps\":[",
+                "{\"character\":\"0\",\"value\":0,\"product\":0},",
+                "{\"character\":\"7\",\"value\":7,\"product\":7},",
+                "{\"character\":null,\"value\":0,\"product\":3}",
+                "],\"checksum\":9,\"check\":\"9\"}\n"
+            ),
+            render(Command::Explain, Format::Json, &records)
+        );
+        assert_eq!(
+            concat!(
+                "input,step,character,value,sum,product,check,error,message\n",
+                "07,1,0,0,,0,9,,\n",
+                "07,2,7,7,,7,9,,\n",
+                "07,3,,0,,3,9,,\n"
+            ),
+            render(Command::Explain, Format::Csv, &records)
+        );
+        let text = render(Command::Explain, Format::Text, &records);
+        assert!(
+            text.contains("checksum = (11 + 1 - 3) mod 11 = 9"),
+            "{}",
+            text
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! # iso_iec_7064::check_digits
+//!
+//! The "98 minus" MOD 97-10 check digits of ISO 13616 (IBAN), which ISO 11649 (RF creditor
+//! references) and ISO 17442 (LEI) also use, and the electronic and print formats shared by IBAN
+//! and RF creditor references.
+//!
+//! The identifier consists of a prefix (such as a country code or "RF"), two check digits, and a
+//! body. The check digits are computed with MOD 97-10 over the body followed by the prefix, with
+//! each letter expanded to two decimal digits (A = 10, ..., Z = 35). MOD 97-10 gives a checksum
+//! from 00 to 96, but these schemes use 98 minus the remainder, which gives 97 and 98 where
+//! MOD 97-10 gives 00 and 01, so the check digits are always in the range 02 to 98. As there is
+//! exactly one value in that range for each remainder, check digits satisfy the check exactly when
+//! they equal the computed ones.
+
+use core::fmt;
+
+use crate::expansion::MOD_97_10_ALPHANUMERIC;
+use crate::normalize::Normalizer;
+use crate::system::System;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// The normalization into electronic format: the print format separates groups with spaces, and
+/// the electronic format is upper-case.
+pub(crate) const ELECTRONIC: Normalizer = Normalizer::NONE.ignore_spaces(true).fold_case(true);
+
+/// Convert a MOD 97-10 checksum into check digits following the "98 minus" convention.
+pub(crate) fn check_digits_98(checksum: u16) -> u8 {
+    let checksum = checksum as u8;
+    if checksum < 2 {
+        checksum + 97
+    } else {
+        checksum
+    }
+}
+
+/// Compute the check digits for an alphanumeric prefix and body.
+pub(crate) fn compute(prefix: &[u8], body: &[u8]) -> u8 {
+    let checksum = MOD_97_10_ALPHANUMERIC
+        .checksum_ascii_bytes_iter(body.iter().chain(prefix).copied())
+        .expect("prefix and body are alphanumeric");
+    check_digits_98(checksum)
+}
+
+/// Parse two check digits, returning `None` unless they are digits in the range 02 to 98.
+pub(crate) fn parse(check: &[u8]) -> Option<u8> {
+    match check {
+        [tens @ b'0'..=b'9', units @ b'0'..=b'9'] => {
+            let value = (tens - b'0') * 10 + (units - b'0');
+            (2..=98).contains(&value).then_some(value)
+        }
+        _ => None,
+    }
+}
+
+/// The two ASCII digits of check digits.
+pub(crate) fn to_ascii(check: u8) -> [u8; 2] {
+    [b'0' + check / 10, b'0' + check % 10]
+}
+
+/// Normalize the input into electronic format in the buffer, or return its normalized length if
+/// that is too long for the buffer.
+pub(crate) fn electronic<'a>(input: &str, buffer: &'a mut [u8]) -> Result<&'a [u8], usize> {
+    let length = ELECTRONIC.normalize_into(input.as_bytes(), buffer);
+    if length > buffer.len() {
+        return Err(length);
+    }
+    Ok(&buffer[..length])
+}
+
+/// The input in electronic format: upper-case, without spaces.
+#[cfg(feature = "alloc")]
+pub(crate) fn to_electronic(input: &str) -> String {
+    ELECTRONIC
+        .normalize_iter(input.as_bytes())
+        .map(char::from)
+        .collect()
+}
+
+/// The input in print format: upper-case, in groups of four characters separated by spaces.
+#[cfg(feature = "alloc")]
+pub(crate) fn to_print(input: &str) -> String {
+    let mut print = String::with_capacity(input.len() + input.len() / 4);
+    for (i, c) in ELECTRONIC.normalize_iter(input.as_bytes()).enumerate() {
+        if i > 0 && i % 4 == 0 {
+            print.push(' ');
+        }
+        print.push(char::from(c));
+    }
+    print
+}
+
+/// Describe check digits outside the range 02 to 98, in the error of the named identifier.
+pub(crate) fn write_invalid(f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+    write!(f, "{} check digits are not 02 to 98", name)
+}
+
+/// Describe an illegal character, in the error of the named identifier.
+pub(crate) fn write_illegal_character(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    position: usize,
+    byte: u8,
+) -> fmt::Result {
+    write!(
+        f,
+        "illegal character {:?} in {} at position {}",
+        byte as char, name, position
+    )
+}
+
+/// Describe check digits that do not satisfy the check, in the error of the named identifier.
+pub(crate) fn write_mismatch(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    expected: u8,
+    found: u8,
+) -> fmt::Result {
+    write!(
+        f,
+        "{} check digits {:02} do not match expected {:02}",
+        name, found, expected
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check_digits::*;
+
+    #[test]
+    fn check_digits_are_98_minus_the_remainder() {
+        assert_eq!(97, check_digits_98(0));
+        assert_eq!(98, check_digits_98(1));
+        assert_eq!(2, check_digits_98(2));
+        assert_eq!(96, check_digits_98(96));
+        assert_eq!(82, compute(b"GB", b"WEST12345698765432"));
+        assert_eq!(18, compute(b"RF", b"539007547034"));
+    }
+
+    #[test]
+    fn parse_accepts_02_to_98() {
+        assert_eq!(Some(2), parse(b"02"));
+        assert_eq!(Some(98), parse(b"98"));
+        assert_eq!(None, parse(b"01"));
+        assert_eq!(None, parse(b"99"));
+        assert_eq!(None, parse(b"9A"));
+        assert_eq!(None, parse(b"9"));
+        assert_eq!(*b"07", to_ascii(7));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn format_check_digits() {
+        assert_eq!("RF18 5390 0754 7034", to_print("rf18539007547034"));
+        assert_eq!(
+            "GB82WEST12345698765432",
+            to_electronic("gb82 west 1234 5698 7654 32")
+        );
+    }
+}
@@ -0,0 +1,324 @@
+//! # iso_iec_7064::checked
+//!
+//! Validated strings that are known to satisfy the check of a _Check Character System_.
+//!
+//! A `Checked<S>` can only be constructed by validating a string with the system `S` (or by
+//! completing a payload with its check character(s)), so code receiving one does not need to
+//! validate it again. The system is identified by its type, through the `StaticSystem` trait, and
+//! the aliases `CheckedMod11_2` and so on name the systems of The Standard:
+//!
+//! ```
+//! use iso_iec_7064::checked::CheckedMod11_2;
+//!
+//! let isni: CheckedMod11_2 = "000000021694233X".parse().unwrap();
+//! assert_eq!("000000021694233", isni.payload());
+//! assert_eq!("X", isni.check());
+//! assert!("0000000216942330".parse::<CheckedMod11_2>().is_err());
+//! ```
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use alloc::string::String;
+
+use crate::dyn_system::DynSystem;
+use crate::error::Error;
+use crate::expansion::AlphanumericExpansion;
+use crate::hybrid::HybridSystem;
+use crate::pure::PureSystem;
+use crate::{
+    MOD_11_10, MOD_11_2, MOD_1271_36, MOD_27_26, MOD_37_2, MOD_37_36, MOD_661_26, MOD_97_10,
+    MOD_97_10_ALPHANUMERIC,
+};
+
+/// A _Check Character System_ type with a single instance, so that the system can be named by its
+/// type alone.
+pub trait StaticSystem: DynSystem + 'static {
+    /// The instance of the system.
+    const INSTANCE: Self;
+}
+
+impl StaticSystem for PureSystem<1, 10, 11, 2> {
+    const INSTANCE: Self = MOD_11_2;
+}
+
+impl StaticSystem for PureSystem<1, 36, 37, 2> {
+    const INSTANCE: Self = MOD_37_2;
+}
+
+impl StaticSystem for PureSystem<2, 9, 97, 10> {
+    const INSTANCE: Self = MOD_97_10;
+}
+
+impl StaticSystem for PureSystem<2, 25, 661, 26> {
+    const INSTANCE: Self = MOD_661_26;
+}
+
+impl StaticSystem for PureSystem<2, 35, 1271, 36> {
+    const INSTANCE: Self = MOD_1271_36;
+}
+
+impl StaticSystem for HybridSystem<9> {
+    const INSTANCE: Self = MOD_11_10;
+}
+
+impl StaticSystem for HybridSystem<25> {
+    const INSTANCE: Self = MOD_27_26;
+}
+
+impl StaticSystem for HybridSystem<35> {
+    const INSTANCE: Self = MOD_37_36;
+}
+
+impl StaticSystem for AlphanumericExpansion<PureSystem<2, 9, 97, 10>> {
+    const INSTANCE: Self = MOD_97_10_ALPHANUMERIC;
+}
+
+/// A string validated with `MOD_11_2`.
+pub type CheckedMod11_2 = Checked<PureSystem<1, 10, 11, 2>>;
+/// A string validated with `MOD_37_2`.
+pub type CheckedMod37_2 = Checked<PureSystem<1, 36, 37, 2>>;
+/// A string validated with `MOD_97_10`.
+pub type CheckedMod97_10 = Checked<PureSystem<2, 9, 97, 10>>;
+/// A string validated with `MOD_661_26`.
+pub type CheckedMod661_26 = Checked<PureSystem<2, 25, 661, 26>>;
+/// A string validated with `MOD_1271_36`.
+pub type CheckedMod1271_36 = Checked<PureSystem<2, 35, 1271, 36>>;
+/// A string validated with `MOD_11_10`.
+pub type CheckedMod11_10 = Checked<HybridSystem<9>>;
+/// A string validated with `MOD_27_26`.
+pub type CheckedMod27_26 = Checked<HybridSystem<25>>;
+/// A string validated with `MOD_37_36`.
+pub type CheckedMod37_36 = Checked<HybridSystem<35>>;
+/// A string validated with `MOD_97_10_ALPHANUMERIC`.
+pub type CheckedMod97_10Alphanumeric = Checked<AlphanumericExpansion<PureSystem<2, 9, 97, 10>>>;
+
+/// A string, with its check character(s) at the end, that satisfies the check of the system `S`.
+pub struct Checked<S> {
+    value: String,
+    system: PhantomData<fn() -> S>,
+}
+
+impl<S> Checked<S>
+where
+    S: StaticSystem,
+{
+    /// Validate a string, which must already have the check character(s) appended.
+    pub fn new(value: String) -> Result<Checked<S>, Error> {
+        S::INSTANCE.try_validate_str(&value)?;
+        Ok(Checked {
+            value,
+            system: PhantomData,
+        })
+    }
+
+    /// Complete a payload with its check character(s).
+    pub fn from_payload(payload: &str) -> Result<Checked<S>, Error> {
+        let system = S::INSTANCE;
+        let checksum = system.try_checksum_str(payload)?;
+        let check = system
+            .render_check_chars(checksum)
+            .expect("checksum is always representable");
+
+        let mut value = String::with_capacity(payload.len() + check.as_str().len());
+        value.push_str(payload);
+        value.push_str(check.as_str());
+        Ok(Checked {
+            value,
+            system: PhantomData,
+        })
+    }
+
+    /// The position where the check character(s) start.
+    fn check_start(&self) -> usize {
+        self.value.len() - S::INSTANCE.system_check_length() as usize
+    }
+
+    /// The complete string.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// The payload: everything before the check character(s).
+    pub fn payload(&self) -> &str {
+        &self.value[..self.check_start()]
+    }
+
+    /// The check character(s).
+    pub fn check(&self) -> &str {
+        &self.value[self.check_start()..]
+    }
+
+    /// Consume, returning the complete string.
+    pub fn into_string(self) -> String {
+        self.value
+    }
+}
+
+impl<S> Clone for Checked<S> {
+    fn clone(&self) -> Self {
+        Checked {
+            value: self.value.clone(),
+            system: PhantomData,
+        }
+    }
+}
+
+impl<S> fmt::Debug for Checked<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Checked").field(&self.value).finish()
+    }
+}
+
+impl<S> fmt::Display for Checked<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl<S> PartialEq for Checked<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<S> Eq for Checked<S> {}
+
+impl<S> PartialOrd for Checked<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Checked<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<S> Hash for Checked<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<S> Deref for Checked<S> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl<S> AsRef<str> for Checked<S> {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl<S> AsRef<[u8]> for Checked<S> {
+    fn as_ref(&self) -> &[u8] {
+        self.value.as_bytes()
+    }
+}
+
+impl<S> From<Checked<S>> for String {
+    fn from(checked: Checked<S>) -> String {
+        checked.value
+    }
+}
+
+impl<S> core::str::FromStr for Checked<S>
+where
+    S: StaticSystem,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Checked::new(s.into())
+    }
+}
+
+impl<S> TryFrom<String> for Checked<S>
+where
+    S: StaticSystem,
+{
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Checked::new(value)
+    }
+}
+
+impl<S> TryFrom<&str> for Checked<S>
+where
+    S: StaticSystem,
+{
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Checked::new(value.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checked::*;
+    use crate::CheckChars;
+
+    #[test]
+    fn only_valid_strings_are_accepted() {
+        assert!("079".parse::<CheckedMod11_2>().is_ok());
+        assert_eq!(
+            Err(Error::CheckMismatch {
+                expected: CheckChars::from_ascii_bytes(b"9").unwrap(),
+                found: CheckChars::from_ascii_bytes(b"8").unwrap(),
+            }),
+            "078".parse::<CheckedMod11_2>()
+        );
+        assert!(CheckedMod97_10::try_from("79444").is_ok());
+        assert!(CheckedMod11_10::try_from("07945").is_ok());
+        assert!(CheckedMod37_36::try_from("A12425GABC1234002M").is_ok());
+        assert!(CheckedMod97_10Alphanumeric::try_from("WEST12345698765432GB82").is_ok());
+        // Letters in the check characters expand to digits that satisfy the check.
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 3,
+                byte: b'K'
+            }),
+            CheckedMod97_10Alphanumeric::try_from("123K")
+        );
+    }
+
+    #[test]
+    fn accessors_split_payload_and_check() {
+        let checked = CheckedMod1271_36::from_payload("ISO79").unwrap();
+        assert_eq!("ISO793W", checked.as_str());
+        assert_eq!("ISO79", checked.payload());
+        assert_eq!("3W", checked.check());
+        assert_eq!(7, checked.len());
+        assert_eq!(b"ISO793W", AsRef::<[u8]>::as_ref(&checked));
+        assert_eq!("ISO793W", String::from(checked));
+    }
+
+    #[test]
+    fn from_payload_reports_reasons() {
+        assert_eq!(
+            Err(Error::TooShort {
+                minimum: 1,
+                length: 0
+            }),
+            CheckedMod11_2::from_payload("")
+        );
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 1,
+                byte: b'A'
+            }),
+            CheckedMod11_2::from_payload("0A")
+        );
+    }
+}
@@ -0,0 +1,429 @@
+//! # iso_iec_7064::cn_resident_id
+//!
+//! Citizen identification numbers of the People's Republic of China, as specified by GB 11643-1999
+//! and printed on Resident Identity Cards, built on `MOD_11_2`.
+//!
+//! A citizen identification number is eighteen characters long: a six-digit administrative
+//! division (region) code from GB/T 2260, an eight-digit birth date (YYYYMMDD), a three-digit
+//! sequence code (odd for men, even for women), and a MOD 11-2 check character, which is a digit
+//! or 'X'. Legacy fifteen-digit numbers omit the century of the birth year (which was always 19)
+//! and the check character; `upgrade` converts them to eighteen characters.
+
+use core::fmt;
+
+use crate::system::check_char;
+use crate::MOD_11_2;
+
+/// The length of a citizen identification number.
+pub const LENGTH: usize = 18;
+
+/// The length of a legacy fifteen-digit number.
+pub const LEGACY_LENGTH: usize = 15;
+
+/// The length of the base of a citizen identification number: everything before the check
+/// character.
+pub const BASE_LENGTH: usize = LENGTH - 1;
+
+/// The province-level codes of GB/T 2260: the first two digits of every region code.
+const PROVINCES: [u8; 34] = [
+    11, 12, 13, 14, 15, 21, 22, 23, 31, 32, 33, 34, 35, 36, 37, 41, 42, 43, 44, 45, 46, 50, 51, 52,
+    53, 54, 61, 62, 63, 64, 65, 71, 81, 82,
+];
+
+/// The earliest birth year accepted.
+pub const MIN_BIRTH_YEAR: u16 = 1800;
+
+/// The latest birth year accepted. Eighteen-character numbers were introduced in 1999, so this
+/// leaves a century of headroom while rejecting implausible years such as 9999.
+pub const MAX_BIRTH_YEAR: u16 = 2099;
+
+/// The reason a citizen identification number was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResidentIdError {
+    /// The input does not have the expected length.
+    WrongLength { expected: usize, length: usize },
+    /// A character other than a digit was encountered at the given (zero-based) position, or a
+    /// character other than a digit or 'X' was encountered as the check character.
+    IllegalCharacter { position: usize, byte: u8 },
+    /// The region code does not start with a province-level code of GB/T 2260.
+    InvalidRegion { code: u32 },
+    /// The birth date is not a valid calendar date from `MIN_BIRTH_YEAR` to `MAX_BIRTH_YEAR`.
+    InvalidBirthDate { year: u16, month: u8, day: u8 },
+    /// The number is well-formed, but the check character does not satisfy the check.
+    CheckMismatch { expected: u8, found: u8 },
+}
+
+impl fmt::Display for ResidentIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResidentIdError::WrongLength { expected, length } => {
+                write!(f, "resident ID length {} should be {}", length, expected)
+            }
+            ResidentIdError::IllegalCharacter { position, byte } => write!(
+                f,
+                "illegal character {:?} in resident ID at position {}",
+                *byte as char, position
+            ),
+            ResidentIdError::InvalidRegion { code } => {
+                write!(f, "invalid resident ID region code {:06}", code)
+            }
+            ResidentIdError::InvalidBirthDate { year, month, day } => write!(
+                f,
+                "invalid resident ID birth date {:04}-{:02}-{:02}",
+                year, month, day
+            ),
+            ResidentIdError::CheckMismatch { expected, found } => write!(
+                f,
+                "resident ID check character {:?} does not match expected {:?}",
+                *found as char, *expected as char
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ResidentIdError {}
+
+/// A birth date, as encoded in a citizen identification number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BirthDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl BirthDate {
+    /// Returns true if this is a valid calendar date from `MIN_BIRTH_YEAR` to `MAX_BIRTH_YEAR`.
+    pub const fn is_valid(&self) -> bool {
+        if self.year < MIN_BIRTH_YEAR
+            || self.year > MAX_BIRTH_YEAR
+            || self.month < 1
+            || self.month > 12
+            || self.day < 1
+        {
+            return false;
+        }
+        let leap = (self.year % 4 == 0 && self.year % 100 != 0) || self.year % 400 == 0;
+        let days = match self.month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        self.day <= days
+    }
+}
+
+impl fmt::Display for BirthDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Parse a run of ASCII digits, which must already have been checked.
+fn number(digits: &[u8]) -> u32 {
+    digits.iter().fold(0, |n, &c| n * 10 + u32::from(c - b'0'))
+}
+
+/// Check that every byte is a digit.
+fn check_digits(bytes: &[u8]) -> Result<(), ResidentIdError> {
+    match bytes.iter().position(|c| !c.is_ascii_digit()) {
+        Some(i) => Err(ResidentIdError::IllegalCharacter {
+            position: i,
+            byte: bytes[i],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Check the region code and birth date of an all-digit base.
+fn check_fields(base: &[u8]) -> Result<(), ResidentIdError> {
+    let province = number(&base[..2]) as u8;
+    if !PROVINCES.contains(&province) {
+        return Err(ResidentIdError::InvalidRegion {
+            code: number(&base[..6]),
+        });
+    }
+    let birth_date = BirthDate {
+        year: number(&base[6..10]) as u16,
+        month: number(&base[10..12]) as u8,
+        day: number(&base[12..14]) as u8,
+    };
+    if !birth_date.is_valid() {
+        let BirthDate { year, month, day } = birth_date;
+        return Err(ResidentIdError::InvalidBirthDate { year, month, day });
+    }
+    Ok(())
+}
+
+/// A valid eighteen-character citizen identification number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResidentId {
+    bytes: [u8; LENGTH],
+}
+
+impl ResidentId {
+    /// Parse and validate an eighteen-character citizen identification number. A lower-case 'x'
+    /// is accepted as the check character.
+    pub fn parse(id: &str) -> Result<ResidentId, ResidentIdError> {
+        let input = id.as_bytes();
+        if input.len() != LENGTH {
+            return Err(ResidentIdError::WrongLength {
+                expected: LENGTH,
+                length: input.len(),
+            });
+        }
+        let mut bytes = [0u8; LENGTH];
+        bytes.copy_from_slice(input);
+        bytes[BASE_LENGTH] = bytes[BASE_LENGTH].to_ascii_uppercase();
+
+        let (base, check) = bytes.split_at(BASE_LENGTH);
+        check_digits(base)?;
+        let found = check[0];
+        if !(found.is_ascii_digit() || found == b'X') {
+            return Err(ResidentIdError::IllegalCharacter {
+                position: BASE_LENGTH,
+                byte: input[BASE_LENGTH],
+            });
+        }
+        check_fields(base)?;
+
+        if MOD_11_2.validate_ascii_bytes(&bytes) {
+            Ok(ResidentId { bytes })
+        } else {
+            Err(ResidentIdError::CheckMismatch {
+                expected: check_char(&MOD_11_2, base.iter().copied()),
+                found,
+            })
+        }
+    }
+
+    /// Convert a legacy fifteen-digit number to eighteen characters, by inserting the century 19
+    /// into the birth year and appending the check character.
+    pub fn upgrade(legacy: &str) -> Result<ResidentId, ResidentIdError> {
+        let input = legacy.as_bytes();
+        if input.len() != LEGACY_LENGTH {
+            return Err(ResidentIdError::WrongLength {
+                expected: LEGACY_LENGTH,
+                length: input.len(),
+            });
+        }
+        check_digits(input)?;
+
+        let mut bytes = [0u8; LENGTH];
+        bytes[..6].copy_from_slice(&input[..6]);
+        bytes[6..8].copy_from_slice(b"19");
+        bytes[8..BASE_LENGTH].copy_from_slice(&input[6..]);
+        check_fields(&bytes[..BASE_LENGTH])?;
+        bytes[BASE_LENGTH] = check_char(&MOD_11_2, bytes[..BASE_LENGTH].iter().copied());
+        Ok(ResidentId { bytes })
+    }
+
+    /// The complete number.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes).expect("resident ID is ASCII")
+    }
+
+    /// The six-digit administrative division code, such as "110105".
+    pub fn region_code(&self) -> &str {
+        &self.as_str()[..6]
+    }
+
+    /// The two-digit province-level code, such as 11 for Beijing.
+    pub fn province_code(&self) -> u8 {
+        number(&self.bytes[..2]) as u8
+    }
+
+    /// The birth date.
+    pub fn birth_date(&self) -> BirthDate {
+        BirthDate {
+            year: number(&self.bytes[6..10]) as u16,
+            month: number(&self.bytes[10..12]) as u8,
+            day: number(&self.bytes[12..14]) as u8,
+        }
+    }
+
+    /// The three-digit sequence code, which is odd for men and even for women.
+    pub fn sequence_code(&self) -> u16 {
+        number(&self.bytes[14..BASE_LENGTH]) as u16
+    }
+
+    /// The check character: a digit or 'X'.
+    pub fn check_character(&self) -> char {
+        char::from(self.bytes[BASE_LENGTH])
+    }
+}
+
+impl fmt::Display for ResidentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl core::str::FromStr for ResidentId {
+    type Err = ResidentIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ResidentId::parse(s)
+    }
+}
+
+/// Validate an eighteen-character citizen identification number, reporting the reason if it is
+/// not valid.
+pub fn validate(id: &str) -> Result<(), ResidentIdError> {
+    ResidentId::parse(id).map(|_| ())
+}
+
+/// Returns true if the citizen identification number is valid.
+pub fn is_valid(id: &str) -> bool {
+    validate(id).is_ok()
+}
+
+/// Compute the check character (a digit or 'X') for the first seventeen digits of a citizen
+/// identification number.
+pub fn check_character(base: &str) -> Result<char, ResidentIdError> {
+    let bytes = base.as_bytes();
+    if bytes.len() != BASE_LENGTH {
+        return Err(ResidentIdError::WrongLength {
+            expected: BASE_LENGTH,
+            length: bytes.len(),
+        });
+    }
+    check_digits(bytes)?;
+    check_fields(bytes)?;
+    Ok(char::from(check_char(&MOD_11_2, bytes.iter().copied())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cn_resident_id::*;
+
+    const EXAMPLES: [&str; 4] = [
+        "11010519491231002X",
+        "440524188001010014",
+        "130503196704010016",
+        "310101200002290015",
+    ];
+
+    #[test]
+    fn validate_examples() {
+        for example in EXAMPLES {
+            assert_eq!(Ok(()), validate(example), "{}", example);
+            assert_eq!(
+                Ok(char::from(example.as_bytes()[BASE_LENGTH])),
+                check_character(&example[..BASE_LENGTH])
+            );
+        }
+        assert!(is_valid("11010519491231002x"));
+    }
+
+    #[test]
+    fn parse_extracts_fields() {
+        let id = ResidentId::parse("11010519491231002X").unwrap();
+        assert_eq!("110105", id.region_code());
+        assert_eq!(11, id.province_code());
+        assert_eq!(
+            BirthDate {
+                year: 1949,
+                month: 12,
+                day: 31
+            },
+            id.birth_date()
+        );
+        assert_eq!(2, id.sequence_code());
+        assert_eq!('X', id.check_character());
+    }
+
+    #[test]
+    fn validate_reports_reasons() {
+        assert_eq!(
+            Err(ResidentIdError::CheckMismatch {
+                expected: b'X',
+                found: b'1'
+            }),
+            validate("110105194912310021")
+        );
+        assert_eq!(
+            Err(ResidentIdError::WrongLength {
+                expected: 18,
+                length: 17
+            }),
+            validate("11010519491231002")
+        );
+        assert_eq!(
+            Err(ResidentIdError::IllegalCharacter {
+                position: 3,
+                byte: b'A'
+            }),
+            validate("110A0519491231002X")
+        );
+        assert_eq!(
+            Err(ResidentIdError::IllegalCharacter {
+                position: 17,
+                byte: b'Y'
+            }),
+            validate("11010519491231002Y")
+        );
+        assert_eq!(
+            Err(ResidentIdError::InvalidRegion { code: 990105 }),
+            validate("99010519491231002X")
+        );
+        assert_eq!(
+            Err(ResidentIdError::InvalidBirthDate {
+                year: 1900,
+                month: 2,
+                day: 29
+            }),
+            validate("310101190002290011")
+        );
+        assert_eq!(
+            Err(ResidentIdError::InvalidBirthDate {
+                year: 9999,
+                month: 1,
+                day: 1
+            }),
+            validate("11010599990101002X")
+        );
+    }
+
+    #[test]
+    fn birth_year_is_bounded() {
+        assert!(check_character("11010520991231002").is_ok());
+        assert_eq!(
+            Err(ResidentIdError::InvalidBirthDate {
+                year: 2100,
+                month: 1,
+                day: 1
+            }),
+            check_character("11010521000101002")
+        );
+        assert_eq!(
+            Err(ResidentIdError::InvalidBirthDate {
+                year: 1799,
+                month: 12,
+                day: 31
+            }),
+            check_character("11010517991231002")
+        );
+    }
+
+    #[test]
+    fn upgrade_legacy_numbers() {
+        assert_eq!(
+            "11010519491231002X",
+            ResidentId::upgrade("110105491231002").unwrap().as_str()
+        );
+        assert_eq!(
+            "130503196704010016",
+            ResidentId::upgrade("130503670401001").unwrap().as_str()
+        );
+        assert_eq!(
+            Err(ResidentIdError::InvalidBirthDate {
+                year: 1967,
+                month: 13,
+                day: 1
+            }),
+            ResidentId::upgrade("130503671301001")
+        );
+    }
+}
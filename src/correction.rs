@@ -0,0 +1,181 @@
+//! # iso_iec_7064::correction
+//!
+//! Suggestions for correcting an input that fails the check, for "did you mean ...?" prompts.
+//!
+//! The _Check Character Systems_ of The Standard detect all single substitution errors and
+//! (nearly) all single adjacent transposition errors, which are also the most common errors made
+//! when transcribing an identifier. So when an input fails the check, the valid strings that
+//! differ from it by one of those errors are good candidates for what was intended:
+//!
+//! ```
+//! use iso_iec_7064::{CorrectionKind, System, MOD_97_10};
+//!
+//! let corrections = MOD_97_10.suggest_corrections("97444");
+//! assert_eq!(1, corrections.len());
+//! assert_eq!("79444", corrections[0].value);
+//! assert_eq!(CorrectionKind::AdjacentTransposition, corrections[0].kind);
+//! ```
+//!
+//! Note that a different error may have been made, so none of the suggestions may be right, and
+//! that there is usually more than one candidate: any position can be changed to satisfy the
+//! check.
+
+use core::fmt;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::analysis::{for_each_variant, ErrorClass, Symbols};
+use crate::system::System;
+
+/// The kind of error a correction undoes. Kinds are ordered from the most to the least common.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CorrectionKind {
+    /// One character was replaced by another.
+    Substitution,
+    /// Two adjacent characters were swapped.
+    AdjacentTransposition,
+}
+
+impl fmt::Display for CorrectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CorrectionKind::Substitution => f.write_str("substitution"),
+            CorrectionKind::AdjacentTransposition => f.write_str("adjacent transposition"),
+        }
+    }
+}
+
+/// A candidate correction of an input that failed the check.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Correction {
+    /// The corrected string, which satisfies the check.
+    pub value: String,
+    /// The kind of error the correction undoes.
+    pub kind: CorrectionKind,
+    /// The (zero-based) position of the changed character, or of the first of the two swapped
+    /// characters.
+    pub position: usize,
+}
+
+/// Collect the strings within one substitution or one adjacent transposition of the input that
+/// satisfy the check, ranked by kind and then by position. Returns nothing for valid inputs. The
+/// candidates are the variants `analysis` applies, so only symbols allowed at each position are
+/// tried.
+pub(crate) fn suggest<const MAX_DIGIT_VALUE: u8, S>(system: &S, string: &str) -> Vec<Correction>
+where
+    S: System<MAX_DIGIT_VALUE> + ?Sized,
+{
+    let mut corrections = Vec::new();
+    if system.validate_string(string) {
+        return corrections;
+    }
+
+    let mut candidate = Vec::from(string.as_bytes());
+    let symbols = Symbols::new(system, candidate.len());
+    for (class, kind) in [
+        (ErrorClass::SingleSubstitution, CorrectionKind::Substitution),
+        (
+            ErrorClass::AdjacentTransposition,
+            CorrectionKind::AdjacentTransposition,
+        ),
+    ] {
+        for_each_variant(class, &mut candidate, &symbols, |variant| {
+            if system.validate_ascii_bytes_iter(variant.iter().copied()) {
+                let position = string
+                    .bytes()
+                    .zip(variant)
+                    .position(|(a, &b)| a != b)
+                    .expect("variants differ from the input");
+                corrections.push(correction(variant, kind, position));
+            }
+        });
+    }
+
+    corrections
+}
+
+fn correction(candidate: &[u8], kind: CorrectionKind, position: usize) -> Correction {
+    Correction {
+        // Only strings made entirely of ALPHABET symbols satisfy the check, so this is ASCII.
+        value: String::from_utf8(Vec::from(candidate)).expect("valid strings are ASCII"),
+        kind,
+        position,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::correction::*;
+    use crate::{MOD_11_10, MOD_11_2, MOD_37_2, MOD_97_10};
+    use alloc::vec;
+
+    fn values(corrections: &[Correction]) -> Vec<&str> {
+        corrections.iter().map(|c| c.value.as_str()).collect()
+    }
+
+    #[test]
+    fn suggest_substitutions_in_every_position() {
+        let corrections = MOD_11_2.suggest_corrections("07941");
+        assert_eq!(
+            vec!["27941", "00941", "07641", "07991", "07940"],
+            values(&corrections)
+        );
+        assert!(corrections
+            .iter()
+            .all(|c| c.kind == CorrectionKind::Substitution));
+        assert_eq!(4, corrections[4].position);
+    }
+
+    #[test]
+    fn suggest_transpositions_after_substitutions() {
+        assert_eq!(
+            vec![Correction {
+                value: "79444".into(),
+                kind: CorrectionKind::AdjacentTransposition,
+                position: 0,
+            }],
+            MOD_97_10.suggest_corrections("97444")
+        );
+
+        for system_corrections in [
+            MOD_11_10.suggest_corrections("70945"),
+            MOD_37_2.suggest_corrections("G123498654421X"),
+        ] {
+            assert!(!system_corrections.is_empty());
+            assert!(system_corrections
+                .windows(2)
+                .all(|pair| pair[0].kind <= pair[1].kind));
+        }
+        assert!(MOD_11_10
+            .suggest_corrections("70945")
+            .iter()
+            .any(|c| c.value == "07945" && c.kind == CorrectionKind::AdjacentTransposition));
+    }
+
+    #[test]
+    fn every_suggestion_is_valid_and_one_error_away() {
+        for input in ["079X1", "0A940", "07940X", "0"] {
+            for correction in MOD_11_2.suggest_corrections(input) {
+                assert!(MOD_11_2.validate_string(&correction.value));
+                assert_eq!(input.len(), correction.value.len());
+                let differences = input
+                    .bytes()
+                    .zip(correction.value.bytes())
+                    .filter(|(a, b)| a != b)
+                    .count();
+                match correction.kind {
+                    CorrectionKind::Substitution => assert_eq!(1, differences),
+                    CorrectionKind::AdjacentTransposition => assert_eq!(2, differences),
+                }
+            }
+        }
+        assert!(values(&MOD_11_2.suggest_corrections("0A940")).contains(&"07940"));
+    }
+
+    #[test]
+    fn nothing_to_suggest_for_valid_input() {
+        assert!(MOD_11_2.suggest_corrections("07940").is_empty());
+        assert!(MOD_11_2.suggest_corrections("").is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+//! # iso_iec_7064::dyn_system
+//!
+//! Object-safe companion to the `System` trait, for choosing _Check Character Systems_ at runtime.
+//!
+//! The `System` trait is generic over the alphabet's maximum digit value and has generic methods,
+//! so different systems cannot be stored together or selected from configuration. `DynSystem`
+//! offers the same operations over byte slices and string slices, and can be used as
+//! `&dyn DynSystem` or `Box<dyn DynSystem>`. Its method names are distinct from those of `System`
+//! so that having both traits in scope never makes a method call ambiguous.
+
+use crate::alphabet::Alphabet;
+use crate::error::Error;
+use crate::expansion::AlphanumericExpansion;
+use crate::hybrid::HybridSystem;
+use crate::pure::PureSystem;
+use crate::system::{CheckChars, System};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// Object-safe trait implemented by all _Check Character Systems_
+pub trait DynSystem {
+    /// The official name of the Check character system. See `System::name`.
+    fn system_name(&self) -> &'static str;
+
+    /// The designation of the Check character system. See `System::designation`.
+    fn system_designation(&self) -> u8;
+
+    /// The alphabet used in the Check character system. See `System::alphabet`.
+    fn system_alphabet(&self) -> Alphabet;
+
+    /// The number of check characters the Check character system produces.
+    fn system_check_length(&self) -> u8;
+
+    /// Validate that the input ASCII bytes, which must already have the check digit(s) appended,
+    /// satisfy the check.
+    fn validate_bytes(&self, bytes: &[u8]) -> bool;
+
+    /// Validate that the input string, which must already have the check digit(s) appended,
+    /// satisfies the check.
+    fn validate_str(&self, string: &str) -> bool {
+        self.validate_bytes(string.as_bytes())
+    }
+
+    /// Validate that the input ASCII bytes satisfy the check, reporting the reason if they do not.
+    fn try_validate_bytes(&self, bytes: &[u8]) -> Result<(), Error>;
+
+    /// Validate that the input string satisfies the check, reporting the reason if it does not.
+    fn try_validate_str(&self, string: &str) -> Result<(), Error> {
+        self.try_validate_bytes(string.as_bytes())
+    }
+
+    /// Compute the checksum for payload ASCII bytes.
+    fn checksum_bytes(&self, bytes: &[u8]) -> Option<u16>;
+
+    /// Compute the checksum for a payload string.
+    fn checksum_str(&self, string: &str) -> Option<u16> {
+        self.checksum_bytes(string.as_bytes())
+    }
+
+    /// Compute the checksum for payload ASCII bytes, reporting the reason if there is none.
+    fn try_checksum_bytes(&self, bytes: &[u8]) -> Result<u16, Error>;
+
+    /// Compute the checksum for a payload string, reporting the reason if there is none.
+    fn try_checksum_str(&self, string: &str) -> Result<u16, Error> {
+        self.try_checksum_bytes(string.as_bytes())
+    }
+
+    /// Render a checksum as check character(s). See `System::check_chars`.
+    fn render_check_chars(&self, checksum: u16) -> Option<CheckChars>;
+
+    /// Compute the check character(s) for a payload string.
+    fn check_chars_str(&self, string: &str) -> Option<CheckChars> {
+        self.checksum_str(string)
+            .and_then(|checksum| self.render_check_chars(checksum))
+    }
+
+    /// Compute the check character(s) for a payload string and return the complete string, with
+    /// the check character(s) appended to the payload.
+    #[cfg(feature = "alloc")]
+    fn complete_str(&self, string: &str) -> Option<String> {
+        let check_chars = self.check_chars_str(string)?;
+        let mut complete = String::with_capacity(string.len() + check_chars.as_bytes().len());
+        complete.push_str(string);
+        complete.push_str(check_chars.as_str());
+        Some(complete)
+    }
+}
+
+/// Implement `DynSystem` for a type that implements `System`, by delegating to it. A blanket
+/// implementation is not possible because the alphabet's maximum digit value would be an
+/// unconstrained parameter.
+macro_rules! impl_dyn_system {
+    ([$($generics:tt)*] $system:ty) => {
+        impl<$($generics)*> DynSystem for $system {
+            fn system_name(&self) -> &'static str {
+                self.name()
+            }
+
+            fn system_designation(&self) -> u8 {
+                self.designation()
+            }
+
+            fn system_alphabet(&self) -> Alphabet {
+                self.alphabet()
+            }
+
+            fn system_check_length(&self) -> u8 {
+                self.check_length()
+            }
+
+            fn validate_bytes(&self, bytes: &[u8]) -> bool {
+                self.validate_ascii_bytes_iter(bytes.iter().copied())
+            }
+
+            fn try_validate_bytes(&self, bytes: &[u8]) -> Result<(), Error> {
+                self.try_validate_ascii_bytes(bytes)
+            }
+
+            fn checksum_bytes(&self, bytes: &[u8]) -> Option<u16> {
+                self.checksum_ascii_bytes_iter(bytes.iter().copied())
+            }
+
+            fn try_checksum_bytes(&self, bytes: &[u8]) -> Result<u16, Error> {
+                self.try_checksum_ascii_bytes(bytes)
+            }
+
+            fn render_check_chars(&self, checksum: u16) -> Option<CheckChars> {
+                self.check_chars(checksum)
+            }
+        }
+    };
+}
+
+impl_dyn_system!(
+    [const CHECK_LENGTH: usize, const MAX_DIGIT_VALUE: u8, const MODULUS: usize, const RADIX: usize]
+    PureSystem<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX>
+);
+impl_dyn_system!([const MAX_DIGIT_VALUE: u8] HybridSystem<MAX_DIGIT_VALUE>);
+impl_dyn_system!([S: System<9>] AlphanumericExpansion<S>);
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::dyn_system::DynSystem;
+    use crate::{MOD_11_10, MOD_11_2, MOD_1271_36, MOD_97_10};
+    use alloc::boxed::Box;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn systems_can_be_stored_together() {
+        let systems: Vec<Box<dyn DynSystem>> =
+            vec![Box::new(MOD_11_2), Box::new(MOD_97_10), Box::new(MOD_11_10)];
+
+        let designations: Vec<u8> = systems.iter().map(|s| s.system_designation()).collect();
+        assert_eq!(vec![1, 3, 6], designations);
+
+        assert!(systems[0].validate_str("079X"));
+        assert!(systems[1].validate_str("79444"));
+        assert!(systems[2].validate_str("07945"));
+    }
+
+    #[test]
+    fn complete_str_through_trait_object() {
+        let system: &dyn DynSystem = &MOD_1271_36;
+        assert_eq!(Some("ISO793W"), system.complete_str("ISO79").as_deref());
+        assert_eq!(Some(140), system.checksum_str("ISO79"));
+    }
+}
@@ -0,0 +1,106 @@
+//! # iso_iec_7064::error
+//!
+//! The reasons an input string may fail validation, or a payload may have no checksum.
+
+use core::fmt;
+
+use crate::system::CheckChars;
+
+/// Structured reason for rejecting an input, as returned by the `try_` methods of `System`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// A character outside the ALPHABET was encountered at the given (zero-based) position.
+    IllegalCharacter { position: usize, byte: u8 },
+    /// A _Supplementary Check Character_ ('X' for MOD 11-2 or '*' for MOD 37-2) was encountered in
+    /// the payload portion of the input, at the given (zero-based) position.
+    SupplementaryCharacterInPayload { position: usize, byte: u8 },
+    /// The input was shorter than the minimum length. There must be at least one payload
+    /// character, plus the check character(s) when validating.
+    TooShort { minimum: usize, length: usize },
+    /// A character that normalization would strip was encountered at the given (zero-based)
+    /// position, and the `Normalizer` is in strict mode.
+    StrippedCharacter { position: usize, byte: u8 },
+    /// The input was well-formed, but its check character(s) do not satisfy the check.
+    CheckMismatch {
+        expected: CheckChars,
+        found: CheckChars,
+    },
+    /// The system rejected well-formed input for a reason not covered above, such as a rule of a
+    /// system implemented outside this crate, or check characters that `CheckChars` cannot hold.
+    Rejected,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::IllegalCharacter { position, byte } => {
+                write!(
+                    f,
+                    "illegal character {:?} at position {}",
+                    *byte as char, position
+                )
+            }
+            Error::SupplementaryCharacterInPayload { position, byte } => {
+                write!(
+                    f,
+                    "supplementary check character {:?} in payload at position {}",
+                    *byte as char, position
+                )
+            }
+            Error::TooShort { minimum, length } => {
+                write!(
+                    f,
+                    "input of length {} is shorter than the minimum length {}",
+                    length, minimum
+                )
+            }
+            Error::StrippedCharacter { position, byte } => {
+                write!(
+                    f,
+                    "character {:?} at position {} would be stripped by normalization",
+                    *byte as char, position
+                )
+            }
+            Error::CheckMismatch { expected, found } => {
+                write!(
+                    f,
+                    "check character(s) {:?} do not match expected {:?}",
+                    found.as_str(),
+                    expected.as_str()
+                )
+            }
+            Error::Rejected => f.write_str("input rejected by the check character system"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// The reason a _Check Character System_ could not be found in the registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LookupError {
+    /// Designation zero, which The Standard reserves for "No check character or non-standard
+    /// system".
+    NoCheck,
+    /// A designation not assigned by The Standard. Designations parsed from strings may be too
+    /// large for a `u8`; those beyond `u32::MAX` are reported as `u32::MAX`.
+    UnknownDesignation(u32),
+    /// A name that does not identify any system defined in The Standard.
+    UnknownName,
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LookupError::NoCheck => f.write_str("no check character or non-standard system"),
+            LookupError::UnknownDesignation(designation) => {
+                write!(f, "unknown designation {}", designation)
+            }
+            LookupError::UnknownName => f.write_str("unknown check character system name"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LookupError {}
@@ -0,0 +1,302 @@
+//! # iso_iec_7064::expansion
+//!
+//! An adapter accepting alphanumeric input for numeric _Check Character Systems_.
+//!
+//! Many uses of MOD 97-10 (IBAN, LEI and ISO 11649 creditor references) first expand letters into
+//! decimal digits before computing the check: each digit stands for itself, and each letter is
+//! replaced by the two decimal digits of its value (A = 10, B = 11, ..., Z = 35). The
+//! `AlphanumericExpansion` adapter performs this expansion on the fly, without allocating, for
+//! any `System` over the numeric alphabet.
+//!
+//! The check characters produced by the adapter are those of the wrapped system, so they are
+//! always digits, and validation rejects input with a letter among the check characters. The rest
+//! of the input, including the check characters, is expanded before validation.
+
+use core::cell::Cell;
+
+use crate::alphabet::Alphabet;
+use crate::error::Error;
+use crate::pure::PureSystem;
+use crate::system::{CheckChars, System};
+use crate::MOD_97_10;
+
+/// `MOD_97_10`, accepting alphanumeric input with letters expanded to two decimal digits.
+pub const MOD_97_10_ALPHANUMERIC: AlphanumericExpansion<PureSystem<2, 9, 97, 10>> =
+    AlphanumericExpansion::new(MOD_97_10);
+
+/// Iterator adapter expanding alphanumeric digit values (0 to 35) into decimal digit values (0 to
+/// 9). A value out of range produces the illegal digit value `u8::MAX`, which makes any `System`
+/// reject the input.
+#[derive(Clone, Debug)]
+pub struct ExpandedDigitValues<I> {
+    inner: I,
+    pending: Option<u8>,
+}
+
+impl<I> ExpandedDigitValues<I>
+where
+    I: Iterator<Item = u8>,
+{
+    /// Expand an iterator of alphanumeric digit values.
+    pub fn new(inner: I) -> ExpandedDigitValues<I> {
+        ExpandedDigitValues {
+            inner,
+            pending: None,
+        }
+    }
+}
+
+impl<I> Iterator for ExpandedDigitValues<I>
+where
+    I: Iterator<Item = u8>,
+{
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if let Some(v) = self.pending.take() {
+            return Some(v);
+        }
+        match self.inner.next()? {
+            v if v < 10 => Some(v),
+            v if v <= Alphabet::Alphanumeric.max_digit_value() => {
+                self.pending = Some(v % 10);
+                Some(v / 10)
+            }
+            _ => Some(u8::MAX),
+        }
+    }
+}
+
+/// Wraps a `System` over the numeric alphabet so that it accepts alphanumeric input, expanding
+/// each letter into the two decimal digits of its value.
+#[derive(Clone, Copy, Debug)]
+pub struct AlphanumericExpansion<S> {
+    inner: S,
+}
+
+impl<S> AlphanumericExpansion<S> {
+    /// Wrap a numeric system.
+    pub const fn new(inner: S) -> AlphanumericExpansion<S> {
+        AlphanumericExpansion { inner }
+    }
+
+    /// The wrapped numeric system.
+    pub const fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S> AlphanumericExpansion<S>
+where
+    S: System<9>,
+{
+    /// Check every byte is alphanumeric, reporting the first that is not.
+    fn check_bytes(bytes: &[u8]) -> Result<(), Error> {
+        match bytes
+            .iter()
+            .position(|&c| Alphabet::Alphanumeric.char_value(c).is_none())
+        {
+            Some(position) => Err(Error::IllegalCharacter {
+                position,
+                byte: bytes[position],
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<S> System<35> for AlphanumericExpansion<S>
+where
+    S: System<9>,
+{
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn designation(&self) -> u8 {
+        self.inner.designation()
+    }
+
+    fn alphabet(&self) -> Alphabet {
+        Alphabet::Alphanumeric
+    }
+
+    fn check_length(&self) -> u8 {
+        self.inner.check_length()
+    }
+
+    /// Letters (values 10 to 35) are rejected among the last `check_length()` values, which are
+    /// the check characters.
+    fn validate_digit_values_iter<I>(&self, it: I) -> bool
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        // The number of values seen since the last letter, which must be at least the number of
+        // check characters once the input is exhausted.
+        let since_letter = Cell::new(usize::MAX);
+        let it = it.into_iter().inspect(|&v| {
+            since_letter.set(if v >= 10 {
+                0
+            } else {
+                since_letter.get().saturating_add(1)
+            })
+        });
+        self.inner
+            .validate_digit_values_iter(ExpandedDigitValues::new(it))
+            && since_letter.get() >= self.check_length() as usize
+    }
+
+    fn checksum_digit_values_iter<I>(&self, it: I) -> Option<u16>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        self.inner
+            .checksum_digit_values_iter(ExpandedDigitValues::new(it.into_iter()))
+    }
+
+    fn try_validate_ascii_bytes(&self, bytes: &[u8]) -> Result<(), Error> {
+        let check_length = self.check_length() as usize;
+        // There must be at least one Payload character before the check character(s).
+        if bytes.len() < (check_length + 1) {
+            return Err(Error::TooShort {
+                minimum: check_length + 1,
+                length: bytes.len(),
+            });
+        }
+        Self::check_bytes(bytes)?;
+
+        // The check characters are those of the wrapped system, so they must be digits.
+        let payload_length = bytes.len() - check_length;
+        if let Some(i) = bytes[payload_length..]
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+        {
+            return Err(Error::IllegalCharacter {
+                position: payload_length + i,
+                byte: bytes[payload_length + i],
+            });
+        }
+
+        if self.validate_ascii_bytes_iter(bytes.iter().copied()) {
+            return Ok(());
+        }
+
+        let expected = self.try_checksum_ascii_bytes(&bytes[..payload_length])?;
+        Err(Error::CheckMismatch {
+            expected: self
+                .check_chars(expected)
+                .expect("checksum is always representable"),
+            found: CheckChars::from_ascii_bytes(&bytes[payload_length..])
+                .expect("check characters are in the alphabet"),
+        })
+    }
+
+    fn try_checksum_ascii_bytes(&self, bytes: &[u8]) -> Result<u16, Error> {
+        // There must be at least one Payload character.
+        if bytes.is_empty() {
+            return Err(Error::TooShort {
+                minimum: 1,
+                length: 0,
+            });
+        }
+        Self::check_bytes(bytes)?;
+
+        Ok(self
+            .checksum_ascii_bytes_iter(bytes.iter().copied())
+            .expect("payload is non-empty and alphanumeric"))
+    }
+
+    /// The check characters are those of the wrapped numeric system.
+    fn check_chars(&self, checksum: u16) -> Option<CheckChars> {
+        self.inner.check_chars(checksum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expansion::*;
+    use crate::MOD_11_10;
+
+    #[test]
+    fn expands_letters_into_two_digits() {
+        let values = [0, 9, 10, 35, 36];
+        let expanded = ExpandedDigitValues::new(values.iter().copied());
+        assert!(expanded.eq([0, 9, 1, 0, 3, 5, u8::MAX]));
+    }
+
+    #[test]
+    fn checksum_matches_expanded_numeric_input() {
+        // "AB" expands to "1011".
+        assert_eq!(
+            MOD_97_10.checksum_string("1011"),
+            MOD_97_10_ALPHANUMERIC.checksum_string("AB")
+        );
+        assert_eq!(
+            MOD_11_10.checksum_string("1011"),
+            AlphanumericExpansion::new(MOD_11_10).checksum_string("AB")
+        );
+    }
+
+    #[test]
+    fn validate_rearranged_iban() {
+        // GB82WEST12345698765432 with the first four characters moved to the end.
+        assert!(MOD_97_10_ALPHANUMERIC.validate_string("WEST12345698765432GB82"));
+        assert!(!MOD_97_10_ALPHANUMERIC.validate_string("WEST12345698765432GB83"));
+    }
+
+    #[test]
+    fn check_chars_are_numeric() {
+        let checksum = MOD_97_10_ALPHANUMERIC.checksum_string("ISO").unwrap();
+        let check_chars = MOD_97_10_ALPHANUMERIC.check_chars(checksum).unwrap();
+        assert!(check_chars.as_bytes().iter().all(u8::is_ascii_digit));
+    }
+
+    #[test]
+    fn try_validate_reports_original_positions() {
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 4,
+                byte: b'-'
+            }),
+            MOD_97_10_ALPHANUMERIC.try_validate_string("WEST-12345698765432GB82")
+        );
+        assert_eq!(
+            Err(Error::CheckMismatch {
+                expected: CheckChars::from_ascii_bytes(b"82").unwrap(),
+                found: CheckChars::from_ascii_bytes(b"83").unwrap(),
+            }),
+            MOD_97_10_ALPHANUMERIC.try_validate_string("WEST12345698765432GB83")
+        );
+    }
+
+    #[test]
+    fn reject_letters_in_check_characters() {
+        // Each of these satisfies the check once expanded.
+        for input in ["123K", "ABC1M", "12345A"] {
+            assert!(!MOD_97_10_ALPHANUMERIC.validate_string(input), "{}", input);
+        }
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 3,
+                byte: b'K'
+            }),
+            MOD_97_10_ALPHANUMERIC.try_validate_string("123K")
+        );
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 5,
+                byte: b'A'
+            }),
+            MOD_97_10_ALPHANUMERIC.try_validate_string("12345A")
+        );
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 4,
+                byte: b'M'
+            }),
+            MOD_97_10_ALPHANUMERIC.try_validate_string("ABC1M")
+        );
+        // Letters are still accepted in the payload.
+        assert!(MOD_97_10_ALPHANUMERIC.validate_string("ABC1M95"));
+    }
+}
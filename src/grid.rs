@@ -0,0 +1,269 @@
+//! # iso_iec_7064::grid
+//!
+//! Global Release Identifiers (GRid), the music industry identifier for digital releases, built on
+//! `MOD_37_36`.
+//!
+//! A GRid is eighteen characters long: the identifier scheme element "A1", a five-character issuer
+//! code, a ten-character release number, and a check character computed with MOD 37,36 over the
+//! first seventeen characters. All characters are letters or digits.
+//!
+//! The canonical form is "A1-2425G-ABC1234002-M". The functions here accept it, with hyphens,
+//! spaces or no separators, in either case. Positions reported in errors refer to the compact form
+//! ("A12425GABC1234002M").
+
+use core::fmt;
+
+use crate::normalize::Normalizer;
+use crate::system::check_char;
+use crate::MOD_37_36;
+
+/// The identifier scheme element at the start of every GRid.
+pub const SCHEME: &str = "A1";
+
+/// The length of the issuer code.
+pub const ISSUER_LENGTH: usize = 5;
+
+/// The length of the release number.
+pub const RELEASE_LENGTH: usize = 10;
+
+/// The length of the base of a GRid: everything before the check character.
+pub const BASE_LENGTH: usize = SCHEME.len() + ISSUER_LENGTH + RELEASE_LENGTH;
+
+/// The length of a GRid in compact form.
+pub const LENGTH: usize = BASE_LENGTH + 1;
+
+/// The normalization applied to input.
+const NORMALIZER: Normalizer = Normalizer::NONE
+    .ignore_spaces(true)
+    .ignore_hyphens(true)
+    .fold_case(true);
+
+/// The reason a GRid was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GridError {
+    /// The input does not have the expected length once separators are removed.
+    WrongLength { expected: usize, length: usize },
+    /// The input does not start with the identifier scheme element "A1".
+    UnknownScheme,
+    /// A character other than a letter or digit was encountered at the given (zero-based)
+    /// position.
+    IllegalCharacter { position: usize, byte: u8 },
+    /// The GRid is well-formed, but the check character does not satisfy the check.
+    CheckMismatch { expected: u8, found: u8 },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::WrongLength { expected, length } => {
+                write!(f, "GRid length {} should be {}", length, expected)
+            }
+            GridError::UnknownScheme => write!(f, "GRid does not start with {}", SCHEME),
+            GridError::IllegalCharacter { position, byte } => write!(
+                f,
+                "illegal character {:?} in GRid at position {}",
+                *byte as char, position
+            ),
+            GridError::CheckMismatch { expected, found } => write!(
+                f,
+                "GRid check character {:?} does not match expected {:?}",
+                *found as char, *expected as char
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GridError {}
+
+/// Normalize the input into the buffer, failing unless it fills the buffer exactly.
+fn compact<'a>(input: &str, buffer: &'a mut [u8]) -> Result<&'a [u8], GridError> {
+    let length = NORMALIZER.normalize_into(input.trim().as_bytes(), buffer);
+    if length != buffer.len() {
+        return Err(GridError::WrongLength {
+            expected: buffer.len(),
+            length,
+        });
+    }
+    Ok(buffer)
+}
+
+/// Check the scheme element, and that every byte is a letter or digit.
+fn check_base(base: &[u8]) -> Result<(), GridError> {
+    if let Some(i) = base
+        .iter()
+        .position(|c| !(c.is_ascii_digit() || c.is_ascii_uppercase()))
+    {
+        return Err(GridError::IllegalCharacter {
+            position: i,
+            byte: base[i],
+        });
+    }
+    if !base.starts_with(SCHEME.as_bytes()) {
+        return Err(GridError::UnknownScheme);
+    }
+    Ok(())
+}
+
+/// A valid GRid, stored in compact form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Grid {
+    bytes: [u8; LENGTH],
+}
+
+impl Grid {
+    /// Parse and validate a GRid in any of the accepted forms.
+    pub fn parse(grid: &str) -> Result<Grid, GridError> {
+        let mut bytes = [0u8; LENGTH];
+        compact(grid, &mut bytes)?;
+
+        let (base, check) = bytes.split_at(BASE_LENGTH);
+        check_base(base)?;
+        let found = check[0];
+        if !(found.is_ascii_digit() || found.is_ascii_uppercase()) {
+            return Err(GridError::IllegalCharacter {
+                position: BASE_LENGTH,
+                byte: found,
+            });
+        }
+
+        if MOD_37_36.validate_ascii_bytes(&bytes) {
+            Ok(Grid { bytes })
+        } else {
+            Err(GridError::CheckMismatch {
+                expected: check_char(&MOD_37_36, base.iter().copied()),
+                found,
+            })
+        }
+    }
+
+    /// Complete a base of seventeen characters, starting with "A1", with its check character.
+    /// Separators are ignored.
+    pub fn generate(base: &str) -> Result<Grid, GridError> {
+        let mut bytes = [0u8; LENGTH];
+        compact(base, &mut bytes[..BASE_LENGTH])?;
+        check_base(&bytes[..BASE_LENGTH])?;
+        bytes[BASE_LENGTH] = check_char(&MOD_37_36, bytes[..BASE_LENGTH].iter().copied());
+        Ok(Grid { bytes })
+    }
+
+    /// The compact form, such as "A12425GABC1234002M".
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes).expect("GRid is ASCII")
+    }
+
+    /// The five-character issuer code.
+    pub fn issuer_code(&self) -> &str {
+        &self.as_str()[SCHEME.len()..SCHEME.len() + ISSUER_LENGTH]
+    }
+
+    /// The ten-character release number.
+    pub fn release_number(&self) -> &str {
+        &self.as_str()[SCHEME.len() + ISSUER_LENGTH..BASE_LENGTH]
+    }
+
+    /// The check character.
+    pub fn check_character(&self) -> char {
+        char::from(self.bytes[BASE_LENGTH])
+    }
+}
+
+/// Formats the GRid in its canonical form, such as "A1-2425G-ABC1234002-M".
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}-{}-{}",
+            SCHEME,
+            self.issuer_code(),
+            self.release_number(),
+            self.check_character()
+        )
+    }
+}
+
+impl core::str::FromStr for Grid {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s)
+    }
+}
+
+/// Validate a GRid in any of the accepted forms, reporting the reason if it is not valid.
+pub fn validate(grid: &str) -> Result<(), GridError> {
+    Grid::parse(grid).map(|_| ())
+}
+
+/// Returns true if the GRid is valid.
+pub fn is_valid(grid: &str) -> bool {
+    validate(grid).is_ok()
+}
+
+/// Compute the check character for the first seventeen characters of a GRid.
+pub fn check_character(base: &str) -> Result<char, GridError> {
+    Grid::generate(base).map(|grid| grid.check_character())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::*;
+
+    #[test]
+    fn validate_examples() {
+        assert_eq!(Ok(()), validate("A1-2425G-ABC1234002-M"));
+        assert_eq!(Ok(()), validate("A12425GABC1234002M"));
+        assert_eq!(Ok(()), validate("a1 2425g abc1234002 m"));
+        assert_eq!(Ok('M'), check_character("A1-2425G-ABC1234002"));
+    }
+
+    #[test]
+    fn parse_exposes_parts() {
+        let grid = Grid::parse("A1-2425G-ABC1234002-M").unwrap();
+        assert_eq!("2425G", grid.issuer_code());
+        assert_eq!("ABC1234002", grid.release_number());
+        assert_eq!('M', grid.check_character());
+    }
+
+    #[test]
+    fn validate_reports_reasons() {
+        assert_eq!(
+            Err(GridError::CheckMismatch {
+                expected: b'M',
+                found: b'N'
+            }),
+            validate("A1-2425G-ABC1234002-N")
+        );
+        assert_eq!(
+            Err(GridError::WrongLength {
+                expected: 18,
+                length: 17
+            }),
+            validate("A1-2425G-ABC1234002")
+        );
+        assert_eq!(
+            Err(GridError::UnknownScheme),
+            validate("B1-2425G-ABC1234002-M")
+        );
+        assert_eq!(
+            Err(GridError::IllegalCharacter {
+                position: 6,
+                byte: b'*'
+            }),
+            validate("A1-2425*-ABC1234002-M")
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn format_canonically() {
+        use alloc::string::ToString;
+
+        let grid = Grid::parse("a12425gabc1234002m").unwrap();
+        assert_eq!("A1-2425G-ABC1234002-M", grid.to_string());
+        assert_eq!(
+            "A1-2425G-ABC1234002-M",
+            Grid::generate("A12425GABC1234002").unwrap().to_string()
+        );
+    }
+}
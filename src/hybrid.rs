@@ -2,15 +2,167 @@
 //!
 //! A trait to help implement the _Hybrid Check Character Systems_ appearing in The Standard.
 
-use crate::system::System;
+use crate::alphabet::Alphabet;
+use crate::error::Error;
+use crate::system::{CheckChars, System};
+
+/// This is the state that will change with each iteration, following the recursive method of The
+/// Standard: each digit value is added to the previous product, reduced modulo M (with zero
+/// replaced by M), and then doubled modulo M + 1 to give the next product.
+#[derive(Clone, Debug)]
+struct State {
+    modulus: usize,
+    /// We maintain the count so we can fail if there isn't at least one payload character.
+    count: usize,
+    /// The product carried into the next iteration.
+    p: usize,
+    /// The sum computed in the most recent iteration.
+    s: usize,
+}
+
+impl State {
+    const fn new(modulus: usize) -> State {
+        State {
+            modulus,
+            count: 0,
+            p: modulus,
+            s: 0,
+        }
+    }
+
+    const fn process_digit_value(&mut self, v: u8) {
+        self.count += 1;
+
+        let s = (self.p + (v as usize)) % self.modulus;
+        self.s = if s == 0 { self.modulus } else { s };
+        self.p = (self.s * 2) % (self.modulus + 1);
+    }
+
+    /// Returns true if the digit values processed so far, which must include the check digit,
+    /// satisfy the check.
+    const fn is_valid(&self) -> bool {
+        // If we have processed fewer than two items, then the input cannot be valid because it has
+        // no Payload.
+        if self.count < 2 {
+            return false;
+        }
+
+        self.s % self.modulus == 1
+    }
+
+    /// Returns the checksum for the payload digit values processed so far, or None if there is
+    /// no Payload.
+    const fn checksum(&self) -> Option<u16> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(((self.modulus + 1 - self.p) % self.modulus) as u16)
+    }
+}
+
+/// Incremental computation for a _Hybrid Check Character System_, for when the input arrives in
+/// several pieces. Obtain one from `HybridSystem::hasher`, feed it ASCII bytes with `update`, and
+/// call `finalize_checksum` or `finalize_validate` as many times as needed. Cloning a hasher
+/// captures the intermediate state so different continuations can be explored.
+#[derive(Clone, Debug)]
+pub struct HybridHasher {
+    alphabet: Alphabet,
+    state: State,
+    /// Set to false once a byte outside the ALPHABET has been encountered, after which no input
+    /// can be valid.
+    ok: bool,
+}
+
+impl HybridHasher {
+    /// Feed more ASCII bytes of the input. If a byte outside the ALPHABET is encountered, the
+    /// remaining input is ignored and both finalize methods will report failure.
+    pub fn update(&mut self, bytes: &[u8]) {
+        if !self.ok {
+            return;
+        }
+        for &c in bytes {
+            match self.alphabet.char_value(c) {
+                Some(v) => self.state.process_digit_value(v),
+                None => {
+                    self.ok = false;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// The checksum for the bytes fed so far, treated as the payload. Returns None if there is no
+    /// payload or it contains characters outside the ALPHABET.
+    pub fn finalize_checksum(&self) -> Option<u16> {
+        if !self.ok {
+            return None;
+        }
+        self.state.checksum()
+    }
+
+    /// Returns true if the bytes fed so far, treated as payload with the check character
+    /// appended, satisfy the check.
+    pub fn finalize_validate(&self) -> bool {
+        self.ok && self.state.is_valid()
+    }
+}
 
 /// Type for implementing all _Hybrid Check Character Systems_
+#[derive(Clone, Copy, Debug)]
 pub struct HybridSystem<const MAX_DIGIT_VALUE: u8> {
     pub(crate) name: &'static str,
     pub(crate) designation: u8,
+    pub(crate) alphabet: Alphabet,
     pub(crate) modulus: usize,
 }
 
+impl<const MAX_DIGIT_VALUE: u8> HybridSystem<MAX_DIGIT_VALUE> {
+    /// Validate that the input ASCII bytes, which must already have the check digit appended,
+    /// satisfy the check. This is a `const fn`, so it can be used to check identifiers at compile
+    /// time (see `const_assert_valid!`).
+    pub const fn validate_ascii_bytes(&self, bytes: &[u8]) -> bool {
+        let mut state = State::new(self.modulus);
+
+        let mut i = 0;
+        while i < bytes.len() {
+            match self.alphabet.char_value(bytes[i]) {
+                Some(v) => state.process_digit_value(v),
+                None => return false,
+            }
+            i += 1;
+        }
+
+        state.is_valid()
+    }
+
+    /// Compute the checksum for payload ASCII bytes. This is a `const fn`, so it can be used to
+    /// compute check digits at compile time.
+    pub const fn checksum_ascii_bytes(&self, bytes: &[u8]) -> Option<u16> {
+        let mut state = State::new(self.modulus);
+
+        let mut i = 0;
+        while i < bytes.len() {
+            match self.alphabet.char_value(bytes[i]) {
+                Some(v) => state.process_digit_value(v),
+                None => return None,
+            }
+            i += 1;
+        }
+
+        state.checksum()
+    }
+
+    /// Start an incremental computation with this system.
+    pub fn hasher(&self) -> HybridHasher {
+        HybridHasher {
+            alphabet: self.alphabet,
+            state: State::new(self.modulus),
+            ok: true,
+        }
+    }
+}
+
 impl<const MAX_DIGIT_VALUE: u8> System<MAX_DIGIT_VALUE> for HybridSystem<MAX_DIGIT_VALUE> {
     fn name(&self) -> &'static str {
         self.name
@@ -20,6 +172,10 @@ impl<const MAX_DIGIT_VALUE: u8> System<MAX_DIGIT_VALUE> for HybridSystem<MAX_DIG
         self.designation
     }
 
+    fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
+
     fn check_length(&self) -> u8 {
         1
     }
@@ -31,27 +187,16 @@ impl<const MAX_DIGIT_VALUE: u8> System<MAX_DIGIT_VALUE> for HybridSystem<MAX_DIG
     where
         I: IntoIterator<Item = u8>,
     {
-        let mut p: usize = self.modulus;
-        let mut s: usize = 0;
-        let mut first_char: bool = true;
+        let mut state = State::new(self.modulus);
 
         for v in it.into_iter() {
             if v > MAX_DIGIT_VALUE {
                 return false;
             }
-
-            if first_char {
-                first_char = false;
-            } else {
-                p = (s * 2) % (self.modulus + 1);
-            }
-
-            s = p + (v as usize);
-            s %= self.modulus;
-            s = if s == 0 { self.modulus } else { s };
+            state.process_digit_value(v);
         }
 
-        s % self.modulus == 1
+        state.is_valid()
     }
 
     /// Compute the checksum for an iterator of payload digit values (for example, values in the
@@ -61,19 +206,71 @@ impl<const MAX_DIGIT_VALUE: u8> System<MAX_DIGIT_VALUE> for HybridSystem<MAX_DIG
     where
         I: IntoIterator<Item = u8>,
     {
-        let mut p: usize = self.modulus;
+        let mut state = State::new(self.modulus);
 
         for v in it.into_iter() {
             if v > MAX_DIGIT_VALUE {
                 return None;
             }
+            state.process_digit_value(v);
+        }
+
+        state.checksum()
+    }
+
+    fn try_validate_ascii_bytes(&self, bytes: &[u8]) -> Result<(), Error> {
+        // There must be at least one Payload character before the check character.
+        if bytes.len() < 2 {
+            return Err(Error::TooShort {
+                minimum: 2,
+                length: bytes.len(),
+            });
+        }
+
+        let mut state = State::new(self.modulus);
+
+        for (position, &byte) in bytes.iter().enumerate() {
+            let v = self
+                .alphabet
+                .char_value(byte)
+                .ok_or(Error::IllegalCharacter { position, byte })?;
+            state.process_digit_value(v);
+        }
+
+        if state.is_valid() {
+            return Ok(());
+        }
+
+        let payload_length = bytes.len() - 1;
+        let expected = self.try_checksum_ascii_bytes(&bytes[..payload_length])?;
+        Err(Error::CheckMismatch {
+            expected: self
+                .check_chars(expected)
+                .expect("checksum is always representable"),
+            found: CheckChars::from_ascii_bytes(&bytes[payload_length..])
+                .expect("check character is in the alphabet"),
+        })
+    }
+
+    fn try_checksum_ascii_bytes(&self, bytes: &[u8]) -> Result<u16, Error> {
+        // There must be at least one Payload character.
+        if bytes.is_empty() {
+            return Err(Error::TooShort {
+                minimum: 1,
+                length: 0,
+            });
+        }
+
+        let mut state = State::new(self.modulus);
 
-            let mut s: usize = p + (v as usize);
-            s %= self.modulus;
-            s = if s == 0 { self.modulus } else { s };
-            p = (s * 2) % (self.modulus + 1);
+        for (position, &byte) in bytes.iter().enumerate() {
+            let v = self
+                .alphabet
+                .char_value(byte)
+                .ok_or(Error::IllegalCharacter { position, byte })?;
+            state.process_digit_value(v);
         }
 
-        Some(((self.modulus + 1 - p) % self.modulus) as u16)
+        Ok(state.checksum().expect("payload is non-empty"))
     }
 }
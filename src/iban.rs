@@ -0,0 +1,827 @@
+//! # iso_iec_7064::iban
+//!
+//! International Bank Account Numbers (IBAN), as specified by ISO 13616-1:2020, built on
+//! `MOD_97_10`.
+//!
+//! An IBAN consists of a two-letter country code, two check digits and a country-specific Basic
+//! Bank Account Number (BBAN). The check digits are computed with MOD 97-10 over the BBAN followed
+//! by the country code, with each letter expanded to two decimal digits (A = 10, ..., Z = 35).
+//! Following ISO 13616, the check digits are "98 minus" the remainder, so they are always in the
+//! range 02 to 98.
+//!
+//! The functions here accept both the electronic format ("GB82WEST12345698765432") and the print
+//! format ("GB82 WEST 1234 5698 7654 32"), in either case. Positions reported in errors refer to
+//! the electronic format.
+
+use core::fmt;
+
+use crate::check_digits;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// The maximum length of an IBAN in electronic format.
+pub const MAX_LENGTH: usize = 34;
+
+/// The name of IBANs in error messages.
+const NAME: &str = "IBAN";
+
+/// The IBAN format of a country, from the IBAN Registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Country {
+    /// The ISO 3166-1 alpha-2 country code.
+    pub code: &'static str,
+    /// The length of the IBAN in electronic format.
+    pub length: usize,
+    /// The structure of the BBAN, in the notation of the IBAN Registry: a sequence of segments of
+    /// the form "4!a", giving a fixed length and a character type: 'n' for digits, 'a' for
+    /// upper-case letters and 'c' for upper-case letters and digits.
+    pub bban_structure: &'static str,
+}
+
+impl Country {
+    /// The length of the BBAN.
+    pub fn bban_length(&self) -> usize {
+        self.length - 4
+    }
+
+    /// Check that a BBAN matches the structure for this country. Positions reported in errors
+    /// refer to the complete IBAN, where the BBAN starts at position 4.
+    pub fn check_bban(&self, bban: &[u8]) -> Result<(), IbanError> {
+        if bban.len() != self.bban_length() {
+            return Err(IbanError::WrongLength {
+                expected: self.length,
+                length: bban.len() + 4,
+            });
+        }
+
+        let mut position = 0;
+        let mut structure = self.bban_structure.as_bytes();
+        while !structure.is_empty() {
+            let digits = structure
+                .iter()
+                .position(|c| !c.is_ascii_digit())
+                .unwrap_or(structure.len());
+            let count: usize = structure[..digits]
+                .iter()
+                .fold(0, |n, c| n * 10 + (c - b'0') as usize);
+            // Skip the '!' marking a fixed length segment.
+            let kind = structure[digits + 1];
+            structure = &structure[digits + 2..];
+
+            for &byte in &bban[position..position + count] {
+                let ok = match kind {
+                    b'n' => byte.is_ascii_digit(),
+                    b'a' => byte.is_ascii_uppercase(),
+                    _ => byte.is_ascii_digit() || byte.is_ascii_uppercase(),
+                };
+                if !ok {
+                    return Err(IbanError::IllegalCharacter {
+                        position: position + 4,
+                        byte,
+                    });
+                }
+                position += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The reason an IBAN was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IbanError {
+    /// The IBAN does not start with a two-letter country code.
+    InvalidCountryCode,
+    /// The country code is not in the IBAN Registry.
+    UnknownCountry { code: [u8; 2] },
+    /// The IBAN does not have the length registered for its country.
+    WrongLength { expected: usize, length: usize },
+    /// The check digits are not two digits in the range 02 to 98.
+    InvalidCheckDigits,
+    /// A character does not match the BBAN structure of the country, at the given (zero-based)
+    /// position.
+    IllegalCharacter { position: usize, byte: u8 },
+    /// The IBAN is well-formed, but the check digits do not satisfy the check.
+    CheckMismatch { expected: u8, found: u8 },
+}
+
+impl fmt::Display for IbanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IbanError::InvalidCountryCode => f.write_str("IBAN does not start with a country code"),
+            IbanError::UnknownCountry { code } => write!(
+                f,
+                "unknown IBAN country code {:?}",
+                core::str::from_utf8(code).unwrap_or("??")
+            ),
+            IbanError::WrongLength { expected, length } => {
+                write!(f, "IBAN length {} should be {}", length, expected)
+            }
+            IbanError::InvalidCheckDigits => check_digits::write_invalid(f, NAME),
+            IbanError::IllegalCharacter { position, byte } => {
+                check_digits::write_illegal_character(f, NAME, *position, *byte)
+            }
+            IbanError::CheckMismatch { expected, found } => {
+                check_digits::write_mismatch(f, NAME, *expected, *found)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IbanError {}
+
+/// The IBAN formats of all countries in the IBAN Registry, in order of country code.
+pub const COUNTRIES: [Country; 88] = [
+    Country {
+        code: "AD",
+        length: 24,
+        bban_structure: "4!n4!n12!c",
+    },
+    Country {
+        code: "AE",
+        length: 23,
+        bban_structure: "3!n16!n",
+    },
+    Country {
+        code: "AL",
+        length: 28,
+        bban_structure: "8!n16!c",
+    },
+    Country {
+        code: "AT",
+        length: 20,
+        bban_structure: "5!n11!n",
+    },
+    Country {
+        code: "AZ",
+        length: 28,
+        bban_structure: "4!a20!c",
+    },
+    Country {
+        code: "BA",
+        length: 20,
+        bban_structure: "3!n3!n8!n2!n",
+    },
+    Country {
+        code: "BE",
+        length: 16,
+        bban_structure: "3!n7!n2!n",
+    },
+    Country {
+        code: "BG",
+        length: 22,
+        bban_structure: "4!a4!n2!n8!c",
+    },
+    Country {
+        code: "BH",
+        length: 22,
+        bban_structure: "4!a14!c",
+    },
+    Country {
+        code: "BI",
+        length: 27,
+        bban_structure: "5!n5!n11!n2!n",
+    },
+    Country {
+        code: "BR",
+        length: 29,
+        bban_structure: "8!n5!n10!n1!a1!c",
+    },
+    Country {
+        code: "BY",
+        length: 28,
+        bban_structure: "4!c4!n16!c",
+    },
+    Country {
+        code: "CH",
+        length: 21,
+        bban_structure: "5!n12!c",
+    },
+    Country {
+        code: "CR",
+        length: 22,
+        bban_structure: "4!n14!n",
+    },
+    Country {
+        code: "CY",
+        length: 28,
+        bban_structure: "3!n5!n16!c",
+    },
+    Country {
+        code: "CZ",
+        length: 24,
+        bban_structure: "4!n6!n10!n",
+    },
+    Country {
+        code: "DE",
+        length: 22,
+        bban_structure: "8!n10!n",
+    },
+    Country {
+        code: "DJ",
+        length: 27,
+        bban_structure: "5!n5!n11!n2!n",
+    },
+    Country {
+        code: "DK",
+        length: 18,
+        bban_structure: "4!n9!n1!n",
+    },
+    Country {
+        code: "DO",
+        length: 28,
+        bban_structure: "4!c20!n",
+    },
+    Country {
+        code: "EE",
+        length: 20,
+        bban_structure: "2!n2!n11!n1!n",
+    },
+    Country {
+        code: "EG",
+        length: 29,
+        bban_structure: "4!n4!n17!n",
+    },
+    Country {
+        code: "ES",
+        length: 24,
+        bban_structure: "4!n4!n1!n1!n10!n",
+    },
+    Country {
+        code: "FI",
+        length: 18,
+        bban_structure: "3!n11!n",
+    },
+    Country {
+        code: "FK",
+        length: 18,
+        bban_structure: "2!a12!n",
+    },
+    Country {
+        code: "FO",
+        length: 18,
+        bban_structure: "4!n9!n1!n",
+    },
+    Country {
+        code: "FR",
+        length: 27,
+        bban_structure: "5!n5!n11!c2!n",
+    },
+    Country {
+        code: "GB",
+        length: 22,
+        bban_structure: "4!a6!n8!n",
+    },
+    Country {
+        code: "GE",
+        length: 22,
+        bban_structure: "2!a16!n",
+    },
+    Country {
+        code: "GI",
+        length: 23,
+        bban_structure: "4!a15!c",
+    },
+    Country {
+        code: "GL",
+        length: 18,
+        bban_structure: "4!n9!n1!n",
+    },
+    Country {
+        code: "GR",
+        length: 27,
+        bban_structure: "3!n4!n16!c",
+    },
+    Country {
+        code: "GT",
+        length: 28,
+        bban_structure: "4!c20!c",
+    },
+    Country {
+        code: "HR",
+        length: 21,
+        bban_structure: "7!n10!n",
+    },
+    Country {
+        code: "HU",
+        length: 28,
+        bban_structure: "3!n4!n1!n15!n1!n",
+    },
+    Country {
+        code: "IE",
+        length: 22,
+        bban_structure: "4!a6!n8!n",
+    },
+    Country {
+        code: "IL",
+        length: 23,
+        bban_structure: "3!n3!n13!n",
+    },
+    Country {
+        code: "IQ",
+        length: 23,
+        bban_structure: "4!a3!n12!n",
+    },
+    Country {
+        code: "IS",
+        length: 26,
+        bban_structure: "4!n2!n6!n10!n",
+    },
+    Country {
+        code: "IT",
+        length: 27,
+        bban_structure: "1!a5!n5!n12!c",
+    },
+    Country {
+        code: "JO",
+        length: 30,
+        bban_structure: "4!a4!n18!c",
+    },
+    Country {
+        code: "KW",
+        length: 30,
+        bban_structure: "4!a22!c",
+    },
+    Country {
+        code: "KZ",
+        length: 20,
+        bban_structure: "3!n13!c",
+    },
+    Country {
+        code: "LB",
+        length: 28,
+        bban_structure: "4!n20!c",
+    },
+    Country {
+        code: "LC",
+        length: 32,
+        bban_structure: "4!a24!c",
+    },
+    Country {
+        code: "LI",
+        length: 21,
+        bban_structure: "5!n12!c",
+    },
+    Country {
+        code: "LT",
+        length: 20,
+        bban_structure: "5!n11!n",
+    },
+    Country {
+        code: "LU",
+        length: 20,
+        bban_structure: "3!n13!c",
+    },
+    Country {
+        code: "LV",
+        length: 21,
+        bban_structure: "4!a13!c",
+    },
+    Country {
+        code: "LY",
+        length: 25,
+        bban_structure: "3!n3!n15!n",
+    },
+    Country {
+        code: "MC",
+        length: 27,
+        bban_structure: "5!n5!n11!c2!n",
+    },
+    Country {
+        code: "MD",
+        length: 24,
+        bban_structure: "2!c18!c",
+    },
+    Country {
+        code: "ME",
+        length: 22,
+        bban_structure: "3!n13!n2!n",
+    },
+    Country {
+        code: "MK",
+        length: 19,
+        bban_structure: "3!n10!c2!n",
+    },
+    Country {
+        code: "MN",
+        length: 20,
+        bban_structure: "4!n12!n",
+    },
+    Country {
+        code: "MR",
+        length: 27,
+        bban_structure: "5!n5!n11!n2!n",
+    },
+    Country {
+        code: "MT",
+        length: 31,
+        bban_structure: "4!a5!n18!c",
+    },
+    Country {
+        code: "MU",
+        length: 30,
+        bban_structure: "4!a2!n2!n12!n3!n3!a",
+    },
+    Country {
+        code: "NI",
+        length: 28,
+        bban_structure: "4!a20!n",
+    },
+    Country {
+        code: "NL",
+        length: 18,
+        bban_structure: "4!a10!n",
+    },
+    Country {
+        code: "NO",
+        length: 15,
+        bban_structure: "4!n6!n1!n",
+    },
+    Country {
+        code: "OM",
+        length: 23,
+        bban_structure: "3!n16!c",
+    },
+    Country {
+        code: "PK",
+        length: 24,
+        bban_structure: "4!a16!c",
+    },
+    Country {
+        code: "PL",
+        length: 28,
+        bban_structure: "8!n16!n",
+    },
+    Country {
+        code: "PS",
+        length: 29,
+        bban_structure: "4!a21!c",
+    },
+    Country {
+        code: "PT",
+        length: 25,
+        bban_structure: "4!n4!n11!n2!n",
+    },
+    Country {
+        code: "QA",
+        length: 29,
+        bban_structure: "4!a21!c",
+    },
+    Country {
+        code: "RO",
+        length: 24,
+        bban_structure: "4!a16!c",
+    },
+    Country {
+        code: "RS",
+        length: 22,
+        bban_structure: "3!n13!n2!n",
+    },
+    Country {
+        code: "RU",
+        length: 33,
+        bban_structure: "9!n5!n15!c",
+    },
+    Country {
+        code: "SA",
+        length: 24,
+        bban_structure: "2!n18!c",
+    },
+    Country {
+        code: "SC",
+        length: 31,
+        bban_structure: "4!a2!n2!n16!n3!a",
+    },
+    Country {
+        code: "SD",
+        length: 18,
+        bban_structure: "2!n12!n",
+    },
+    Country {
+        code: "SE",
+        length: 24,
+        bban_structure: "3!n16!n1!n",
+    },
+    Country {
+        code: "SI",
+        length: 19,
+        bban_structure: "5!n8!n2!n",
+    },
+    Country {
+        code: "SK",
+        length: 24,
+        bban_structure: "4!n6!n10!n",
+    },
+    Country {
+        code: "SM",
+        length: 27,
+        bban_structure: "1!a5!n5!n12!c",
+    },
+    Country {
+        code: "SO",
+        length: 23,
+        bban_structure: "4!n3!n12!n",
+    },
+    Country {
+        code: "ST",
+        length: 25,
+        bban_structure: "4!n4!n11!n2!n",
+    },
+    Country {
+        code: "SV",
+        length: 28,
+        bban_structure: "4!a20!n",
+    },
+    Country {
+        code: "TL",
+        length: 23,
+        bban_structure: "3!n14!n2!n",
+    },
+    Country {
+        code: "TN",
+        length: 24,
+        bban_structure: "2!n3!n13!n2!n",
+    },
+    Country {
+        code: "TR",
+        length: 26,
+        bban_structure: "5!n1!n16!c",
+    },
+    Country {
+        code: "UA",
+        length: 29,
+        bban_structure: "6!n19!c",
+    },
+    Country {
+        code: "VA",
+        length: 22,
+        bban_structure: "3!n15!n",
+    },
+    Country {
+        code: "VG",
+        length: 24,
+        bban_structure: "4!a16!n",
+    },
+    Country {
+        code: "XK",
+        length: 20,
+        bban_structure: "4!n10!n2!n",
+    },
+    Country {
+        code: "YE",
+        length: 30,
+        bban_structure: "4!a4!n18!c",
+    },
+];
+
+/// Look up the IBAN format of a country by its ISO 3166-1 alpha-2 code (in either case).
+pub fn country(code: &str) -> Option<&'static Country> {
+    COUNTRIES
+        .iter()
+        .find(|country| country.code.eq_ignore_ascii_case(code))
+}
+
+/// Normalize the input into the electronic format, in the buffer provided. Returns as much as
+/// fits in the buffer, and the full normalized length, so that input that is too long can still be
+/// checked against the length of its country.
+fn electronic<'a>(iban: &str, buffer: &'a mut [u8; MAX_LENGTH]) -> (&'a [u8], usize) {
+    let length = check_digits::ELECTRONIC.normalize_into(iban.as_bytes(), buffer);
+    (&buffer[..length.min(MAX_LENGTH)], length)
+}
+
+/// Look up the country of an IBAN (or of a country code) in electronic format.
+fn country_of(bytes: &[u8]) -> Result<&'static Country, IbanError> {
+    if bytes.len() < 2 || !bytes[0].is_ascii_uppercase() || !bytes[1].is_ascii_uppercase() {
+        return Err(IbanError::InvalidCountryCode);
+    }
+    let code = [bytes[0], bytes[1]];
+    COUNTRIES
+        .iter()
+        .find(|country| country.code.as_bytes() == code)
+        .ok_or(IbanError::UnknownCountry { code })
+}
+
+/// Validate an IBAN in electronic or print format, reporting the reason if it is not valid.
+pub fn validate(iban: &str) -> Result<(), IbanError> {
+    let mut buffer = [0u8; MAX_LENGTH];
+    let (iban, length) = electronic(iban, &mut buffer);
+
+    let country = country_of(iban)?;
+    if length != country.length {
+        return Err(IbanError::WrongLength {
+            expected: country.length,
+            length,
+        });
+    }
+
+    let (code, check, bban) = (&iban[..2], &iban[2..4], &iban[4..]);
+    let found = check_digits::parse(check).ok_or(IbanError::InvalidCheckDigits)?;
+
+    country.check_bban(bban)?;
+
+    let expected = check_digits::compute(code, bban);
+    if expected == found {
+        Ok(())
+    } else {
+        Err(IbanError::CheckMismatch { expected, found })
+    }
+}
+
+/// Returns true if the IBAN, in electronic or print format, is valid.
+pub fn is_valid(iban: &str) -> bool {
+    validate(iban).is_ok()
+}
+
+/// Compute the IBAN check digits (02 to 98) for a country code and BBAN. The BBAN must match the
+/// structure registered for the country. Spaces are ignored and letters are folded to upper-case.
+pub fn check_digits(country_code: &str, bban: &str) -> Result<u8, IbanError> {
+    let mut code_buffer = [0u8; MAX_LENGTH];
+    let (code, length) = electronic(country_code, &mut code_buffer);
+    if length != 2 {
+        return Err(IbanError::InvalidCountryCode);
+    }
+    let country = country_of(code)?;
+
+    let mut bban_buffer = [0u8; MAX_LENGTH];
+    let (bban, length) = electronic(bban, &mut bban_buffer);
+    if length != bban.len() {
+        return Err(IbanError::WrongLength {
+            expected: country.length,
+            length: length + 4,
+        });
+    }
+    country.check_bban(bban)?;
+
+    Ok(check_digits::compute(code, bban))
+}
+
+/// Build a complete IBAN, in electronic format, from a country code and BBAN.
+#[cfg(feature = "alloc")]
+pub fn generate(country_code: &str, bban: &str) -> Result<String, IbanError> {
+    let check = check_digits(country_code, bban)?;
+    let mut iban = String::with_capacity(MAX_LENGTH);
+    iban.push_str(&check_digits::to_electronic(country_code));
+    iban.extend(check_digits::to_ascii(check).map(char::from));
+    iban.push_str(&check_digits::to_electronic(bban));
+    Ok(iban)
+}
+
+/// Validate an IBAN and return it in electronic format: upper-case, without spaces.
+#[cfg(feature = "alloc")]
+pub fn to_electronic(iban: &str) -> Result<String, IbanError> {
+    validate(iban)?;
+    Ok(check_digits::to_electronic(iban))
+}
+
+/// Validate an IBAN and return it in print format: upper-case, in groups of four characters
+/// separated by spaces.
+#[cfg(feature = "alloc")]
+pub fn to_print(iban: &str) -> Result<String, IbanError> {
+    validate(iban)?;
+    Ok(check_digits::to_print(iban))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::iban::*;
+
+    // Examples from the IBAN Registry.
+    const EXAMPLES: [&str; 32] = [
+        "GB82WEST12345698765432",
+        "DE89370400440532013000",
+        "FR1420041010050500013M02606",
+        "NL91ABNA0417164300",
+        "BE68539007547034",
+        "CH9300762011623852957",
+        "ES9121000418450200051332",
+        "IT60X0542811101000000123456",
+        "NO9386011117947",
+        "MT84MALT011000012345MTLCAST001S",
+        "SA0380000000608010167519",
+        "BR1800360305000010009795493C1",
+        "PL61109010140000071219812874",
+        "SE4550000000058398257466",
+        "AT611904300234573201",
+        "LU280019400644750000",
+        "IE29AIBK93115212345678",
+        "FI2112345600000785",
+        "DK5000400440116243",
+        "PT50000201231234567890154",
+        "GR1601101250000000012300695",
+        "HR1210010051863000160",
+        "CZ6508000000192000145399",
+        "HU42117730161111101800000000",
+        "RO49AAAA1B31007593840000",
+        "TR330006100519786457841326",
+        "KW81CBKU0000000000001234560101",
+        "QA58DOHB00001234567890ABCDEFG",
+        "MU17BOMM0101101030300200000MUR",
+        "SC18SSCB11010000000000001497USD",
+        "LC55HEMM000100010012001200023015",
+        "UA213223130000026007233566001",
+    ];
+
+    #[test]
+    fn registry_lengths_match_bban_structures() {
+        for country in COUNTRIES {
+            let mut total = 0;
+            for segment in country.bban_structure.split_inclusive(['n', 'a', 'c']) {
+                total += segment[..segment.len() - 2].parse::<usize>().unwrap();
+            }
+            assert_eq!(country.bban_length(), total, "{}", country.code);
+        }
+        assert!(COUNTRIES.windows(2).all(|w| w[0].code < w[1].code));
+    }
+
+    #[test]
+    fn validate_registry_examples() {
+        for example in EXAMPLES {
+            assert_eq!(Ok(()), validate(example), "{}", example);
+        }
+    }
+
+    #[test]
+    fn validate_print_format_and_lower_case() {
+        assert!(is_valid("GB82 WEST 1234 5698 7654 32"));
+        assert!(is_valid("gb82 west 1234 5698 7654 32"));
+    }
+
+    #[test]
+    fn validate_reports_reasons() {
+        assert_eq!(
+            Err(IbanError::CheckMismatch {
+                expected: 82,
+                found: 83
+            }),
+            validate("GB83WEST12345698765432")
+        );
+        assert_eq!(
+            Err(IbanError::WrongLength {
+                expected: 22,
+                length: 21
+            }),
+            validate("GB82WEST1234569876543")
+        );
+        // Input too long for any country still reports the length of its own.
+        assert_eq!(
+            Err(IbanError::WrongLength {
+                expected: 22,
+                length: 40
+            }),
+            validate("DE89 3704 0044 0532 0130 0012 3456 7890 1234 5678")
+        );
+        assert_eq!(
+            Err(IbanError::WrongLength {
+                expected: 22,
+                length: 44
+            }),
+            check_digits("DE", "3704004405320130001234567890123456789012")
+        );
+        assert_eq!(
+            Err(IbanError::UnknownCountry { code: *b"ZZ" }),
+            validate("ZZ82WEST12345698765432")
+        );
+        assert_eq!(Err(IbanError::InvalidCountryCode), validate("1282"));
+        assert_eq!(
+            Err(IbanError::IllegalCharacter {
+                position: 4,
+                byte: b'1'
+            }),
+            validate("GB821EST12345698765432")
+        );
+        assert_eq!(
+            Err(IbanError::InvalidCheckDigits),
+            validate("GB01WEST12345698765432")
+        );
+    }
+
+    #[test]
+    fn check_digits_examples() {
+        assert_eq!(Ok(82), check_digits("GB", "WEST12345698765432"));
+        assert_eq!(Ok(89), check_digits("de", "370400440532013000"));
+        for example in EXAMPLES {
+            let expected: u8 = example[2..4].parse().unwrap();
+            assert_eq!(Ok(expected), check_digits(&example[..2], &example[4..]));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn generate_and_format() {
+        assert_eq!(
+            Ok("GB82WEST12345698765432"),
+            generate("gb", "west 1234 5698 7654 32").as_deref()
+        );
+        assert_eq!(
+            Ok("GB82 WEST 1234 5698 7654 32"),
+            to_print("GB82WEST12345698765432").as_deref()
+        );
+        assert_eq!(
+            Ok("GB82WEST12345698765432"),
+            to_electronic("gb82 west 1234 5698 7654 32").as_deref()
+        );
+    }
+}
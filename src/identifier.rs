@@ -0,0 +1,682 @@
+//! # iso_iec_7064::identifier
+//!
+//! Declarative profiles for identifiers protected by a _Check Character System_.
+//!
+//! Most identifier schemes are a payload of fixed or variable length over some character set,
+//! perhaps with a literal prefix, protected by one of the systems of The Standard, and written in
+//! groups for display. An `Identifier` describes such a scheme as data, and provides validation,
+//! generation and formatting for it:
+//!
+//! ```
+//! use iso_iec_7064::identifier::{
+//!     CheckConvention, CheckPosition, Identifier, PrefixCheck, PrefixMode,
+//! };
+//! use iso_iec_7064::{Alphabet, Normalizer, MOD_11_2};
+//!
+//! const ISNI: Identifier = Identifier {
+//!     name: "ISNI",
+//!     system: &MOD_11_2,
+//!     check_convention: CheckConvention::System,
+//!     payload_alphabet: Alphabet::Numeric,
+//!     min_length: 15,
+//!     max_length: 15,
+//!     prefix: "",
+//!     prefix_mode: PrefixMode::Required,
+//!     prefix_check: PrefixCheck::Excluded,
+//!     check_position: CheckPosition::Trailing,
+//!     normalizer: Normalizer::LENIENT,
+//!     grouping: &[4],
+//!     separator: '-',
+//!     rule: None,
+//! };
+//!
+//! assert!(ISNI.is_valid("0000 0002 1694 233x"));
+//! # #[cfg(feature = "alloc")]
+//! assert_eq!(Ok("0000-0002-1694-233X".into()), ISNI.format("000000021694233X"));
+//! ```
+//!
+//! The `national_id` module defines profiles for national identifiers in the same way.
+//!
+//! Positions reported in errors refer to the normalized input, including any prefix.
+
+use core::fmt;
+
+use crate::alphabet::Alphabet;
+use crate::check_digits;
+use crate::dyn_system::DynSystem;
+use crate::error::Error;
+use crate::normalize::Normalizer;
+use crate::system::CheckChars;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// The maximum length of a normalized identifier, including its prefix and check characters.
+pub const MAX_LENGTH: usize = 64;
+
+/// Where the check character(s) sit in the identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CheckPosition {
+    /// After the payload, at the end of the identifier.
+    Trailing,
+    /// Before the payload, immediately after the prefix.
+    Leading,
+}
+
+/// Whether, and where, the prefix takes part in the check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrefixCheck {
+    /// The check covers only the payload.
+    Excluded,
+    /// The check covers the prefix followed by the payload.
+    Prepended,
+    /// The check covers the payload followed by the prefix. With
+    /// `CheckConvention::NinetyEightMinus`, this describes ISO 11649 creditor references.
+    Appended,
+}
+
+/// Whether the prefix must be written, and whether it is part of the normalized identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrefixMode {
+    /// The identifier must start with the prefix.
+    Required,
+    /// The prefix may be omitted from input, but is part of the normalized identifier.
+    Optional,
+    /// The prefix may be written in front of the identifier (such as a country code in front of a
+    /// national number), but is not part of the normalized identifier.
+    Dropped,
+}
+
+/// How the check characters are derived from the checksum of the system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CheckConvention {
+    /// The check characters of the system, as specified in The Standard.
+    System,
+    /// Two check digits from 02 to 98, as used with MOD 97-10 by IBAN, LEI and ISO 11649 creditor
+    /// references: 98 minus the remainder, which gives 97 and 98 where The Standard gives 00 and
+    /// 01. The system must have two numeric check characters.
+    NinetyEightMinus,
+}
+
+/// A structural rule, checked on the normalized identifier without its prefix, and returning a
+/// message describing the rule broken.
+pub type Rule = fn(&[u8]) -> Result<(), &'static str>;
+
+/// The reason an identifier was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IdentifierError {
+    /// The identifier does not start with the prefix of the profile.
+    MissingPrefix,
+    /// The payload is shorter or longer than the profile allows.
+    WrongLength {
+        minimum: usize,
+        maximum: usize,
+        length: usize,
+    },
+    /// A character that is not allowed was encountered at the given (zero-based) position.
+    IllegalCharacter { position: usize, byte: u8 },
+    /// The identifier breaks the structural rule of the profile, described by the message.
+    InvalidStructure(&'static str),
+    /// The system rejected the identifier, usually with `Error::CheckMismatch`, or the normalizer
+    /// rejected it with `Error::StrippedCharacter`.
+    Check(Error),
+}
+
+impl fmt::Display for IdentifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdentifierError::MissingPrefix => f.write_str("identifier prefix is missing"),
+            IdentifierError::WrongLength {
+                minimum,
+                maximum,
+                length,
+            } if minimum == maximum => {
+                write!(f, "payload length {} should be {}", length, minimum)
+            }
+            IdentifierError::WrongLength {
+                minimum,
+                maximum,
+                length,
+            } => write!(
+                f,
+                "payload length {} should be from {} to {}",
+                length, minimum, maximum
+            ),
+            IdentifierError::IllegalCharacter { position, byte } => write!(
+                f,
+                "illegal character {:?} in identifier at position {}",
+                *byte as char, position
+            ),
+            IdentifierError::InvalidStructure(message) => f.write_str(message),
+            IdentifierError::Check(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IdentifierError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IdentifierError::Check(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for IdentifierError {
+    fn from(error: Error) -> Self {
+        IdentifierError::Check(error)
+    }
+}
+
+/// A declarative profile of an identifier scheme.
+#[derive(Clone, Copy)]
+pub struct Identifier {
+    /// The name of the identifier scheme.
+    pub name: &'static str,
+    /// The _Check Character System_ protecting the identifier.
+    pub system: &'static dyn DynSystem,
+    /// How the check characters are derived from the checksum of the system.
+    pub check_convention: CheckConvention,
+    /// The alphabet the payload characters must belong to, which may be narrower than that of the
+    /// system.
+    pub payload_alphabet: Alphabet,
+    /// The minimum length of the payload, excluding the prefix and check character(s).
+    pub min_length: usize,
+    /// The maximum length of the payload, excluding the prefix and check character(s).
+    pub max_length: usize,
+    /// The literal prefix of every identifier (in normalized form), or "" if there is none.
+    pub prefix: &'static str,
+    /// Whether the prefix must be written, and whether it is part of the normalized identifier.
+    pub prefix_mode: PrefixMode,
+    /// Whether, and where, the prefix takes part in the check.
+    pub prefix_check: PrefixCheck,
+    /// Where the check character(s) sit.
+    pub check_position: CheckPosition,
+    /// The normalization applied to input.
+    pub normalizer: Normalizer,
+    /// The sizes of the groups when formatting, applied from the start of the identifier
+    /// (including its prefix). The last size repeats until the identifier is exhausted, and an
+    /// empty grouping formats without separators.
+    pub grouping: &'static [usize],
+    /// The separator between groups when formatting.
+    pub separator: char,
+    /// An additional structural rule, checked before the check characters.
+    pub rule: Option<Rule>,
+}
+
+impl fmt::Debug for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Identifier")
+            .field("name", &self.name)
+            .field("system", &self.system.system_name())
+            .field("check_convention", &self.check_convention)
+            .field("payload_alphabet", &self.payload_alphabet)
+            .field("min_length", &self.min_length)
+            .field("max_length", &self.max_length)
+            .field("prefix", &self.prefix)
+            .field("prefix_mode", &self.prefix_mode)
+            .field("prefix_check", &self.prefix_check)
+            .field("check_position", &self.check_position)
+            .field("normalizer", &self.normalizer)
+            .field("grouping", &self.grouping)
+            .field("separator", &self.separator)
+            .field("rule", &self.rule.is_some())
+            .finish()
+    }
+}
+
+/// A fixed-capacity byte buffer, so that validation does not allocate.
+struct Buffer {
+    bytes: [u8; MAX_LENGTH],
+    length: usize,
+}
+
+impl Buffer {
+    fn new() -> Buffer {
+        Buffer {
+            bytes: [0u8; MAX_LENGTH],
+            length: 0,
+        }
+    }
+
+    /// Append bytes, returning false if they do not fit.
+    fn extend<I>(&mut self, bytes: I) -> bool
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        for c in bytes {
+            if self.length == MAX_LENGTH {
+                return false;
+            }
+            self.bytes[self.length] = c;
+            self.length += 1;
+        }
+        true
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.length]
+    }
+}
+
+impl Identifier {
+    /// The number of check characters.
+    fn check_length(&self) -> usize {
+        self.system.system_check_length() as usize
+    }
+
+    /// The error for a payload of the wrong length.
+    fn wrong_length(&self, length: usize) -> IdentifierError {
+        IdentifierError::WrongLength {
+            minimum: self.min_length,
+            maximum: self.max_length,
+            length,
+        }
+    }
+
+    /// Normalize the input into the buffer.
+    fn normalize(&self, input: &str, buffer: &mut Buffer) -> Result<(), IdentifierError> {
+        let bytes = input.as_bytes();
+        self.normalizer.check_strict(bytes)?;
+        if !buffer.extend(self.normalizer.normalize_iter(bytes)) {
+            let length = self.normalizer.normalize_iter(bytes).count();
+            let overhead = self.prefix.len() + self.check_length();
+            return Err(self.wrong_length(length.saturating_sub(overhead)));
+        }
+        Ok(())
+    }
+
+    /// Check the length of the payload, and that its characters are in the payload alphabet,
+    /// reporting positions offset by `start`.
+    fn check_payload(&self, payload: &[u8], start: usize) -> Result<(), IdentifierError> {
+        if payload.len() < self.min_length || payload.len() > self.max_length {
+            return Err(self.wrong_length(payload.len()));
+        }
+        let alphabet = self.system.system_alphabet();
+        match payload.iter().position(|&c| {
+            !self.payload_alphabet.is_payload_char(c) || !alphabet.is_payload_char(c)
+        }) {
+            Some(i) => Err(IdentifierError::IllegalCharacter {
+                position: start + i,
+                byte: payload[i],
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Arrange the payload (and the check characters, if any) in the order the system checks
+    /// them, failing if the result is longer than `MAX_LENGTH`.
+    fn checked(
+        &self,
+        payload: &[u8],
+        check: &[u8],
+        buffer: &mut Buffer,
+    ) -> Result<(), IdentifierError> {
+        let prefix = self.prefix.as_bytes();
+        let fits = match self.prefix_check {
+            PrefixCheck::Excluded => buffer.extend(payload.iter().copied()),
+            PrefixCheck::Prepended => buffer.extend(prefix.iter().chain(payload).copied()),
+            PrefixCheck::Appended => buffer.extend(payload.iter().chain(prefix).copied()),
+        } && buffer.extend(check.iter().copied());
+        if fits {
+            Ok(())
+        } else {
+            Err(self.wrong_length(payload.len()))
+        }
+    }
+
+    /// Split any prefix from a normalized identifier, returning the rest and the position at
+    /// which it starts.
+    fn strip_prefix<'a>(&self, compact: &'a [u8]) -> Result<(&'a [u8], usize), IdentifierError> {
+        match compact.strip_prefix(self.prefix.as_bytes()) {
+            Some(rest) => Ok((rest, self.prefix.len())),
+            None if self.prefix_mode == PrefixMode::Required => Err(IdentifierError::MissingPrefix),
+            None => Ok((compact, 0)),
+        }
+    }
+
+    /// Check the structural rule, if any, on the identifier without its prefix.
+    fn check_rule(&self, body: &[u8]) -> Result<(), IdentifierError> {
+        match self.rule {
+            Some(rule) => rule(body).map_err(IdentifierError::InvalidStructure),
+            None => Ok(()),
+        }
+    }
+
+    /// Split a normalized identifier into its payload and check characters, checking both and the
+    /// structural rule.
+    fn split<'a>(&self, compact: &'a [u8]) -> Result<(&'a [u8], &'a [u8]), IdentifierError> {
+        let (body, start) = self.strip_prefix(compact)?;
+        let check_length = self.check_length();
+        if body.len() < check_length {
+            return Err(self.wrong_length(0));
+        }
+
+        let (payload, check, payload_start, check_start) = match self.check_position {
+            CheckPosition::Trailing => {
+                let (payload, check) = body.split_at(body.len() - check_length);
+                (payload, check, start, start + payload.len())
+            }
+            CheckPosition::Leading => {
+                let (check, payload) = body.split_at(check_length);
+                (payload, check, start + check_length, start)
+            }
+        };
+        self.check_payload(payload, payload_start)?;
+
+        let alphabet = self.system.system_alphabet();
+        if let Some(i) = check.iter().position(|&c| alphabet.char_value(c).is_none()) {
+            return Err(IdentifierError::IllegalCharacter {
+                position: check_start + i,
+                byte: check[i],
+            });
+        }
+        self.check_rule(body)?;
+        Ok((payload, check))
+    }
+
+    /// Compute the check characters for a payload that has already been checked.
+    fn compute(&self, payload: &[u8]) -> Result<CheckChars, IdentifierError> {
+        let mut checked = Buffer::new();
+        self.checked(payload, &[], &mut checked)?;
+        let checksum = self.system.try_checksum_bytes(checked.as_bytes())?;
+        Ok(match self.check_convention {
+            CheckConvention::System => self.system.render_check_chars(checksum),
+            CheckConvention::NinetyEightMinus => CheckChars::from_ascii_bytes(
+                &check_digits::to_ascii(check_digits::check_digits_98(checksum)),
+            ),
+        }
+        .expect("checksum is always representable"))
+    }
+
+    /// Write the payload and check characters in order into the buffer.
+    fn assemble(&self, payload: &[u8], check: &CheckChars, buffer: &mut Buffer) {
+        let fits = match self.check_position {
+            CheckPosition::Trailing => {
+                buffer.extend(payload.iter().chain(check.as_bytes()).copied())
+            }
+            CheckPosition::Leading => {
+                buffer.extend(check.as_bytes().iter().chain(payload).copied())
+            }
+        };
+        debug_assert!(fits, "payload was checked against MAX_LENGTH");
+    }
+
+    /// Validate an identifier, reporting the reason if it is not valid.
+    pub fn validate(&self, input: &str) -> Result<(), IdentifierError> {
+        let mut compact = Buffer::new();
+        self.normalize(input, &mut compact)?;
+        let (payload, check) = self.split(compact.as_bytes())?;
+
+        match self.check_convention {
+            CheckConvention::System => {
+                let mut checked = Buffer::new();
+                self.checked(payload, check, &mut checked)?;
+                Ok(self.system.try_validate_bytes(checked.as_bytes())?)
+            }
+            CheckConvention::NinetyEightMinus => {
+                let expected = self.compute(payload)?;
+                // The expected check digits are always from 02 to 98, so they are the only ones
+                // that satisfy the check.
+                if check == expected.as_bytes() {
+                    Ok(())
+                } else {
+                    Err(IdentifierError::Check(Error::CheckMismatch {
+                        expected,
+                        found: CheckChars::from_ascii_bytes(check)
+                            .expect("check characters are in the alphabet"),
+                    }))
+                }
+            }
+        }
+    }
+
+    /// Returns true if the identifier is valid.
+    pub fn is_valid(&self, input: &str) -> bool {
+        self.validate(input).is_ok()
+    }
+
+    /// Compute the check character(s) for a payload. The payload must not include the prefix,
+    /// unless the prefix is optional or dropped.
+    pub fn check_chars(&self, payload: &str) -> Result<CheckChars, IdentifierError> {
+        let mut compact = Buffer::new();
+        self.normalize(payload, &mut compact)?;
+        let (payload, start) = match self.prefix_mode {
+            PrefixMode::Required => (compact.as_bytes(), 0),
+            _ => self.strip_prefix(compact.as_bytes())?,
+        };
+        self.check_payload(payload, start)?;
+
+        let check = self.compute(payload)?;
+        if self.rule.is_some() {
+            let mut body = Buffer::new();
+            self.assemble(payload, &check, &mut body);
+            self.check_rule(body.as_bytes())?;
+        }
+        Ok(check)
+    }
+
+    /// Generate a complete identifier, in normalized form, from a payload. The payload must not
+    /// include the prefix, unless the prefix is optional or dropped.
+    #[cfg(feature = "alloc")]
+    pub fn generate(&self, payload: &str) -> Result<String, IdentifierError> {
+        let check = self.check_chars(payload)?;
+        let mut compact = Buffer::new();
+        self.normalize(payload, &mut compact)?;
+        let payload = match self.prefix_mode {
+            PrefixMode::Required => compact.as_bytes(),
+            _ => self.strip_prefix(compact.as_bytes())?.0,
+        };
+
+        let mut body = Buffer::new();
+        self.assemble(payload, &check, &mut body);
+        Ok(self.write(body.as_bytes()))
+    }
+
+    /// Write the identifier, from the body without its prefix, in normalized form.
+    #[cfg(feature = "alloc")]
+    fn write(&self, body: &[u8]) -> String {
+        let mut s = String::with_capacity(self.prefix.len() + body.len());
+        if self.prefix_mode != PrefixMode::Dropped {
+            s.push_str(self.prefix);
+        }
+        s.push_str(core::str::from_utf8(body).expect("identifier is ASCII"));
+        s
+    }
+
+    /// Normalize a valid identifier.
+    #[cfg(feature = "alloc")]
+    pub fn compact(&self, input: &str) -> Result<String, IdentifierError> {
+        self.validate(input)?;
+        let mut compact = Buffer::new();
+        self.normalize(input, &mut compact)?;
+        let (body, _) = self.strip_prefix(compact.as_bytes())?;
+        Ok(self.write(body))
+    }
+
+    /// Format a valid identifier in groups.
+    #[cfg(feature = "alloc")]
+    pub fn format(&self, input: &str) -> Result<String, IdentifierError> {
+        let compact = self.compact(input)?;
+        let mut s = String::with_capacity(2 * compact.len());
+        let mut sizes = self.grouping.iter().copied();
+        let mut size = sizes.next().unwrap_or(usize::MAX);
+        let mut count = 0;
+        for c in compact.chars() {
+            if count == size {
+                s.push(self.separator);
+                size = sizes.next().unwrap_or(size);
+                count = 0;
+            }
+            s.push(c);
+            count += 1;
+        }
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::identifier::*;
+    use crate::{MOD_11_2, MOD_37_36, MOD_97_10_ALPHANUMERIC};
+
+    const ISNI: Identifier = Identifier {
+        name: "ISNI",
+        system: &MOD_11_2,
+        check_convention: CheckConvention::System,
+        payload_alphabet: Alphabet::Numeric,
+        min_length: 15,
+        max_length: 15,
+        prefix: "",
+        prefix_mode: PrefixMode::Required,
+        prefix_check: PrefixCheck::Excluded,
+        check_position: CheckPosition::Trailing,
+        normalizer: Normalizer::LENIENT,
+        grouping: &[4],
+        separator: '-',
+        rule: None,
+    };
+
+    const RF: Identifier = Identifier {
+        name: "ISO 11649 creditor reference",
+        system: &MOD_97_10_ALPHANUMERIC,
+        check_convention: CheckConvention::NinetyEightMinus,
+        payload_alphabet: Alphabet::Alphanumeric,
+        min_length: 1,
+        max_length: 21,
+        prefix: "RF",
+        prefix_mode: PrefixMode::Required,
+        prefix_check: PrefixCheck::Appended,
+        check_position: CheckPosition::Leading,
+        normalizer: Normalizer::NONE.ignore_spaces(true).fold_case(true),
+        grouping: &[4],
+        separator: ' ',
+        rule: None,
+    };
+
+    const GRID: Identifier = Identifier {
+        name: "GRid",
+        system: &MOD_37_36,
+        check_convention: CheckConvention::System,
+        payload_alphabet: Alphabet::Alphanumeric,
+        min_length: 15,
+        max_length: 15,
+        prefix: "A1",
+        prefix_mode: PrefixMode::Required,
+        prefix_check: PrefixCheck::Prepended,
+        check_position: CheckPosition::Trailing,
+        normalizer: Normalizer::LENIENT,
+        grouping: &[2, 5, 10, 1],
+        separator: '-',
+        rule: None,
+    };
+
+    #[test]
+    fn validate_examples() {
+        assert_eq!(Ok(()), ISNI.validate("0000-0002-1825-0097"));
+        assert_eq!(Ok(()), ISNI.validate("0000 0002 1694 233x"));
+        assert_eq!(Ok(()), RF.validate("RF18 5390 0754 7034"));
+        assert_eq!(Ok(()), GRID.validate("A1-2425G-ABC1234002-M"));
+    }
+
+    #[test]
+    fn validate_reports_reasons() {
+        assert_eq!(
+            Err(IdentifierError::Check(Error::CheckMismatch {
+                expected: CheckChars::from_ascii_bytes(b"7").unwrap(),
+                found: CheckChars::from_ascii_bytes(b"8").unwrap(),
+            })),
+            ISNI.validate("0000-0002-1825-0098")
+        );
+        assert_eq!(
+            Err(IdentifierError::WrongLength {
+                minimum: 15,
+                maximum: 15,
+                length: 14
+            }),
+            ISNI.validate("0000-0002-1825-009")
+        );
+        assert_eq!(
+            Err(IdentifierError::IllegalCharacter {
+                position: 4,
+                byte: b'X'
+            }),
+            ISNI.validate("0000X00218250097")
+        );
+        assert_eq!(
+            Err(IdentifierError::MissingPrefix),
+            RF.validate("RS18 5390 0754 7034")
+        );
+        assert_eq!(
+            Err(IdentifierError::IllegalCharacter {
+                position: 3,
+                byte: b'*'
+            }),
+            RF.validate("RF1*539007547034")
+        );
+        // The Standard would give check digits 01 for this reference.
+        assert_eq!(
+            Err(IdentifierError::Check(Error::CheckMismatch {
+                expected: CheckChars::from_ascii_bytes(b"98").unwrap(),
+                found: CheckChars::from_ascii_bytes(b"01").unwrap(),
+            })),
+            RF.validate("RF0154")
+        );
+        assert_eq!(
+            Err(IdentifierError::WrongLength {
+                minimum: 15,
+                maximum: 15,
+                length: 67
+            }),
+            GRID.validate("A10000000000000000000000000000000000000000000000000000000000000000000M")
+        );
+    }
+
+    #[test]
+    fn check_chars_examples() {
+        assert_eq!("X", ISNI.check_chars("000000021694233").unwrap().as_str());
+        assert_eq!("18", RF.check_chars("539007547034").unwrap().as_str());
+        assert_eq!("98", RF.check_chars("54").unwrap().as_str());
+        assert_eq!("M", GRID.check_chars("2425GABC1234002").unwrap().as_str());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn generate_and_format() {
+        assert_eq!(
+            Ok("000000021694233X"),
+            ISNI.generate("0000-0002-1694-233").as_deref()
+        );
+        assert_eq!(
+            Ok("RF18539007547034"),
+            RF.generate("5390 0754 7034").as_deref()
+        );
+        assert_eq!(
+            Ok("A12425GABC1234002M"),
+            GRID.generate("2425GABC1234002").as_deref()
+        );
+        assert_eq!(
+            Ok("RF18 5390 0754 7034"),
+            RF.format("rf18539007547034").as_deref()
+        );
+        assert_eq!(
+            Ok("A1-2425G-ABC1234002-M"),
+            GRID.format("a12425gabc1234002m").as_deref()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn rf_matches_rf_reference() {
+        use crate::rf_reference;
+        use alloc::string::ToString;
+
+        for x in 0..2000 {
+            let reference = x.to_string();
+            let rf = rf_reference::generate(&reference);
+            assert_eq!(rf.as_deref().ok(), RF.generate(&reference).as_deref().ok());
+            assert_eq!(Ok(()), RF.validate(&rf.unwrap()));
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! # iso_iec_7064::isan
+//!
+//! International Standard Audiovisual Numbers (ISAN), as specified by ISO 15706, built on
+//! `MOD_37_36`.
+//!
+//! An ISAN consists of a twelve-digit root identifying the work and a four-digit episode (or part)
+//! number, followed by a check character computed with MOD 37,36 over those sixteen digits. A
+//! version-identifying ISAN (V-ISAN) appends an eight-digit version number and a second check
+//! character computed over all twenty-four digits (excluding the first check character). All
+//! digits are hexadecimal.
+//!
+//! The canonical form is "ISAN 0000-0001-8947-0000-8" (or
+//! "ISAN 0000-0001-8947-0000-8-0000-0000-D" for a V-ISAN). The functions here accept it, with or
+//! without the "ISAN" prefix, with hyphens, spaces or no separators, in either case. Positions
+//! reported in errors refer to the compact form ("0000000189470000" followed by the check
+//! character, version and second check character).
+
+use core::fmt;
+
+use crate::normalize::{strip_prefix_ignore_case, Normalizer};
+use crate::system::{check_char, System};
+use crate::MOD_37_36;
+
+/// The number of hexadecimal digits in the root.
+pub const ROOT_LENGTH: usize = 12;
+
+/// The number of hexadecimal digits in the episode.
+pub const EPISODE_LENGTH: usize = 4;
+
+/// The number of hexadecimal digits in the version.
+pub const VERSION_LENGTH: usize = 8;
+
+/// The length of an ISAN without version in compact form, including its check character.
+pub const LENGTH: usize = ROOT_LENGTH + EPISODE_LENGTH + 1;
+
+/// The length of a V-ISAN in compact form, including both check characters.
+pub const VERSIONED_LENGTH: usize = LENGTH + VERSION_LENGTH + 1;
+
+/// The prefix of the canonical form.
+pub const PREFIX: &str = "ISAN";
+
+/// The normalization applied to input.
+const NORMALIZER: Normalizer = Normalizer::NONE
+    .ignore_spaces(true)
+    .ignore_hyphens(true)
+    .fold_case(true);
+
+/// The reason an ISAN was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IsanError {
+    /// The input does not have an accepted length once the prefix and separators are removed: 17
+    /// (ISAN) or 26 (V-ISAN) characters when parsing, 16 or 8 digits when generating.
+    WrongLength { length: usize },
+    /// A character other than a hexadecimal digit was encountered at the given (zero-based)
+    /// position, or a character other than a letter or digit was encountered as a check
+    /// character.
+    IllegalCharacter { position: usize, byte: u8 },
+    /// The ISAN is well-formed, but the check character at the given (zero-based) position does
+    /// not satisfy the check.
+    CheckMismatch {
+        position: usize,
+        expected: u8,
+        found: u8,
+    },
+}
+
+impl fmt::Display for IsanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IsanError::WrongLength { length } => write!(f, "invalid ISAN length {}", length),
+            IsanError::IllegalCharacter { position, byte } => write!(
+                f,
+                "illegal character {:?} in ISAN at position {}",
+                *byte as char, position
+            ),
+            IsanError::CheckMismatch {
+                position,
+                expected,
+                found,
+            } => write!(
+                f,
+                "ISAN check character {:?} at position {} does not match expected {:?}",
+                *found as char, position, *expected as char
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IsanError {}
+
+/// Remove any "ISAN" prefix, then normalize the rest into the buffer, returning its length
+/// (which may exceed the length of the buffer).
+fn compact(input: &str, buffer: &mut [u8; VERSIONED_LENGTH]) -> usize {
+    let input = input.trim();
+    let input = strip_prefix_ignore_case(input, PREFIX).unwrap_or(input);
+    NORMALIZER.normalize_into(input.as_bytes(), buffer)
+}
+
+/// Check that every byte is an (upper-case) hexadecimal digit, reporting positions offset by
+/// `start`.
+fn check_hex(bytes: &[u8], start: usize) -> Result<(), IsanError> {
+    match bytes
+        .iter()
+        .position(|c| !(c.is_ascii_digit() || (b'A'..=b'F').contains(c)))
+    {
+        Some(i) => Err(IsanError::IllegalCharacter {
+            position: start + i,
+            byte: bytes[i],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Verify the check character at `position` against the digits it covers.
+fn check<I>(digits: I, position: usize, found: u8) -> Result<(), IsanError>
+where
+    I: IntoIterator<Item = u8> + Clone,
+{
+    if !(found.is_ascii_digit() || found.is_ascii_uppercase()) {
+        return Err(IsanError::IllegalCharacter {
+            position,
+            byte: found,
+        });
+    }
+    if MOD_37_36.validate_ascii_bytes_iter(digits.clone().into_iter().chain([found])) {
+        Ok(())
+    } else {
+        Err(IsanError::CheckMismatch {
+            position,
+            expected: check_char(&MOD_37_36, digits),
+            found,
+        })
+    }
+}
+
+/// A valid ISAN or V-ISAN, stored in compact form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Isan {
+    bytes: [u8; VERSIONED_LENGTH],
+    versioned: bool,
+}
+
+impl Isan {
+    /// Parse and validate an ISAN or V-ISAN in any of the accepted forms.
+    pub fn parse(isan: &str) -> Result<Isan, IsanError> {
+        let mut bytes = [0u8; VERSIONED_LENGTH];
+        let length = compact(isan, &mut bytes);
+        if length != LENGTH && length != VERSIONED_LENGTH {
+            return Err(IsanError::WrongLength { length });
+        }
+        let versioned = length == VERSIONED_LENGTH;
+
+        let work = &bytes[..LENGTH - 1];
+        check_hex(work, 0)?;
+        if versioned {
+            check_hex(&bytes[LENGTH..VERSIONED_LENGTH - 1], LENGTH)?;
+        }
+        check(work.iter().copied(), LENGTH - 1, bytes[LENGTH - 1])?;
+        if versioned {
+            let digits = work
+                .iter()
+                .chain(&bytes[LENGTH..VERSIONED_LENGTH - 1])
+                .copied();
+            check(digits, VERSIONED_LENGTH - 1, bytes[VERSIONED_LENGTH - 1])?;
+        }
+
+        Ok(Isan { bytes, versioned })
+    }
+
+    /// Complete a root and episode (sixteen hexadecimal digits), and optionally a version (eight
+    /// hexadecimal digits), with their check characters. Separators are ignored.
+    pub fn generate(root_episode: &str, version: Option<&str>) -> Result<Isan, IsanError> {
+        let mut bytes = [0u8; VERSIONED_LENGTH];
+
+        let mut buffer = [0u8; VERSIONED_LENGTH];
+        let length = compact(root_episode, &mut buffer);
+        if length != LENGTH - 1 {
+            return Err(IsanError::WrongLength { length });
+        }
+        check_hex(&buffer[..length], 0)?;
+        bytes[..LENGTH - 1].copy_from_slice(&buffer[..length]);
+        bytes[LENGTH - 1] = check_char(&MOD_37_36, buffer[..length].iter().copied());
+
+        let versioned = version.is_some();
+        if let Some(version) = version {
+            let mut buffer = [0u8; VERSIONED_LENGTH];
+            let length = compact(version, &mut buffer);
+            if length != VERSION_LENGTH {
+                return Err(IsanError::WrongLength { length });
+            }
+            check_hex(&buffer[..length], LENGTH)?;
+            bytes[LENGTH..VERSIONED_LENGTH - 1].copy_from_slice(&buffer[..length]);
+            bytes[VERSIONED_LENGTH - 1] = check_char(
+                &MOD_37_36,
+                bytes[..LENGTH - 1]
+                    .iter()
+                    .chain(&bytes[LENGTH..VERSIONED_LENGTH - 1])
+                    .copied(),
+            );
+        }
+
+        Ok(Isan { bytes, versioned })
+    }
+
+    /// The compact form, such as "00000001894700008".
+    pub fn as_str(&self) -> &str {
+        let length = if self.versioned {
+            VERSIONED_LENGTH
+        } else {
+            LENGTH
+        };
+        core::str::from_utf8(&self.bytes[..length]).expect("ISAN is ASCII")
+    }
+
+    /// The twelve-digit root, identifying the work.
+    pub fn root(&self) -> &str {
+        &self.as_str()[..ROOT_LENGTH]
+    }
+
+    /// The four-digit episode or part number.
+    pub fn episode(&self) -> &str {
+        &self.as_str()[ROOT_LENGTH..LENGTH - 1]
+    }
+
+    /// The eight-digit version number, if this is a V-ISAN.
+    pub fn version(&self) -> Option<&str> {
+        if self.versioned {
+            Some(&self.as_str()[LENGTH..VERSIONED_LENGTH - 1])
+        } else {
+            None
+        }
+    }
+
+    /// The check character over the root and episode.
+    pub fn check_character(&self) -> char {
+        char::from(self.bytes[LENGTH - 1])
+    }
+
+    /// The check character over the root, episode and version, if this is a V-ISAN.
+    pub fn version_check_character(&self) -> Option<char> {
+        if self.versioned {
+            Some(char::from(self.bytes[VERSIONED_LENGTH - 1]))
+        } else {
+            None
+        }
+    }
+}
+
+/// Formats the ISAN in its canonical form, such as "ISAN 0000-0001-8947-0000-8".
+impl fmt::Display for Isan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.as_str();
+        write!(
+            f,
+            "{} {}-{}-{}-{}-{}",
+            PREFIX,
+            &s[0..4],
+            &s[4..8],
+            &s[8..12],
+            &s[12..16],
+            &s[16..17]
+        )?;
+        if self.versioned {
+            write!(f, "-{}-{}-{}", &s[17..21], &s[21..25], &s[25..26])?;
+        }
+        Ok(())
+    }
+}
+
+impl core::str::FromStr for Isan {
+    type Err = IsanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Isan::parse(s)
+    }
+}
+
+/// Validate an ISAN or V-ISAN in any of the accepted forms, reporting the reason if it is not
+/// valid.
+pub fn validate(isan: &str) -> Result<(), IsanError> {
+    Isan::parse(isan).map(|_| ())
+}
+
+/// Returns true if the ISAN or V-ISAN is valid.
+pub fn is_valid(isan: &str) -> bool {
+    validate(isan).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::isan::*;
+
+    #[test]
+    fn validate_examples() {
+        for example in [
+            "ISAN 0000-0001-8947-0000-8-0000-0000-D",
+            "ISAN 0000-0001-8947-0000-8",
+            "0000-0000-3A8D-0000-Z-0000-0000-6",
+            "isan 0000 0000 3a8d 0000 z",
+            "000000003A8D0000Z000000006",
+            " ISAN 0000-0001-8947-0000-8\n",
+            "0000-0000-3A8D-0000-Z-0000-0000-6\t",
+        ] {
+            assert_eq!(Ok(()), validate(example), "{}", example);
+        }
+    }
+
+    #[test]
+    fn parse_exposes_parts() {
+        let isan = Isan::parse("ISAN 0000-0001-8947-0000-8-0000-0000-D").unwrap();
+        assert_eq!("000000018947", isan.root());
+        assert_eq!("0000", isan.episode());
+        assert_eq!(Some("00000000"), isan.version());
+        assert_eq!('8', isan.check_character());
+        assert_eq!(Some('D'), isan.version_check_character());
+
+        let isan = Isan::parse("000000018947 0000 8").unwrap();
+        assert_eq!(None, isan.version());
+        assert_eq!(None, isan.version_check_character());
+    }
+
+    #[test]
+    fn validate_reports_reasons() {
+        assert_eq!(
+            Err(IsanError::CheckMismatch {
+                position: 16,
+                expected: b'8',
+                found: b'9'
+            }),
+            validate("ISAN 0000-0001-8947-0000-9-0000-0000-D")
+        );
+        assert_eq!(
+            Err(IsanError::CheckMismatch {
+                position: 25,
+                expected: b'D',
+                found: b'E'
+            }),
+            validate("ISAN 0000-0001-8947-0000-8-0000-0000-E")
+        );
+        assert_eq!(
+            Err(IsanError::WrongLength { length: 16 }),
+            validate("ISAN 0000-0001-8947-0000")
+        );
+        assert_eq!(
+            Err(IsanError::IllegalCharacter {
+                position: 4,
+                byte: b'G'
+            }),
+            validate("ISAN 0000-G001-8947-0000-8")
+        );
+        assert_eq!(
+            Err(IsanError::IllegalCharacter {
+                position: 16,
+                byte: b'*'
+            }),
+            validate("ISAN 0000-0001-8947-0000-*")
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn generate_and_format() {
+        use alloc::string::ToString;
+
+        let isan = Isan::generate("0000-0001-8947-0000", Some("0000-0000")).unwrap();
+        assert_eq!("ISAN 0000-0001-8947-0000-8-0000-0000-D", isan.to_string());
+        assert_eq!(
+            Err(IsanError::WrongLength { length: 12 }),
+            Isan::generate("0000-0001-8947", None)
+        );
+        let isan = Isan::generate("00000000 3a8d 0000", None).unwrap();
+        assert_eq!("ISAN 0000-0000-3A8D-0000-Z", isan.to_string());
+    }
+}
@@ -0,0 +1,353 @@
+//! # iso_iec_7064::isni
+//!
+//! International Standard Name Identifiers (ISNI), as specified by ISO 27729:2012, and ORCID iDs,
+//! which are ISNIs allocated from a reserved block, built on `MOD_11_2`.
+//!
+//! An ISNI is sixteen characters long: fifteen digits followed by a MOD 11-2 check character,
+//! which is a digit or 'X'. It is written compactly ("0000000218250097"), in groups of four
+//! separated by hyphens ("0000-0002-1825-0097", the form used by ORCID) or spaces
+//! ("0000 0002 1825 0097", the form used by ISNI), or as a URL
+//! (`https://orcid.org/0000-0002-1825-0097` or `https://isni.org/isni/0000000218250097`). The
+//! functions here accept all of these forms, URLs with or without "www." in any case, and a
+//! lower-case 'x'. Positions reported in errors refer to the compact form.
+
+use core::fmt;
+
+use crate::normalize::{strip_prefix_ignore_case, Normalizer};
+use crate::system::check_char;
+use crate::MOD_11_2;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// The length of an ISNI in compact form.
+pub const LENGTH: usize = 16;
+
+/// The length of the base of an ISNI: everything before the check character.
+pub const BASE_LENGTH: usize = LENGTH - 1;
+
+/// The URL prefixes accepted in front of an ISNI or ORCID iD, in any case.
+pub const URL_PREFIXES: [&str; 12] = [
+    "https://orcid.org/",
+    "http://orcid.org/",
+    "orcid.org/",
+    "https://www.orcid.org/",
+    "http://www.orcid.org/",
+    "www.orcid.org/",
+    "https://isni.org/isni/",
+    "http://isni.org/isni/",
+    "https://www.isni.org/isni/",
+    "http://www.isni.org/isni/",
+    "www.isni.org/isni/",
+    "isni.org/isni/",
+];
+
+/// The normalization applied to input after removing any URL prefix.
+const NORMALIZER: Normalizer = Normalizer::NONE
+    .ignore_spaces(true)
+    .ignore_hyphens(true)
+    .fold_case(true);
+
+/// The reason an ISNI was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IsniError {
+    /// The input does not have the expected length once separators are removed.
+    WrongLength { expected: usize, length: usize },
+    /// A character other than a digit was encountered at the given (zero-based) position, or a
+    /// character other than a digit or 'X' was encountered as the check character.
+    IllegalCharacter { position: usize, byte: u8 },
+    /// The ISNI is well-formed, but the check character does not satisfy the check.
+    CheckMismatch { expected: u8, found: u8 },
+}
+
+impl fmt::Display for IsniError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IsniError::WrongLength { expected, length } => {
+                write!(f, "ISNI length {} should be {}", length, expected)
+            }
+            IsniError::IllegalCharacter { position, byte } => write!(
+                f,
+                "illegal character {:?} in ISNI at position {}",
+                *byte as char, position
+            ),
+            IsniError::CheckMismatch { expected, found } => write!(
+                f,
+                "ISNI check character {:?} does not match expected {:?}",
+                *found as char, *expected as char
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IsniError {}
+
+/// Remove any URL prefix, then normalize the rest into the buffer, failing unless it fills the
+/// buffer exactly.
+fn compact<'a>(input: &str, buffer: &'a mut [u8]) -> Result<&'a [u8], IsniError> {
+    let input = input.trim();
+    let input = URL_PREFIXES
+        .iter()
+        .find_map(|prefix| strip_prefix_ignore_case(input, prefix))
+        .unwrap_or(input);
+
+    let length = NORMALIZER.normalize_into(input.as_bytes(), buffer);
+    if length != buffer.len() {
+        return Err(IsniError::WrongLength {
+            expected: buffer.len(),
+            length,
+        });
+    }
+    Ok(buffer)
+}
+
+/// Check that every byte of the base is a digit.
+fn check_base(base: &[u8]) -> Result<(), IsniError> {
+    match base.iter().position(|c| !c.is_ascii_digit()) {
+        Some(i) => Err(IsniError::IllegalCharacter {
+            position: i,
+            byte: base[i],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// A valid ISNI or ORCID iD, stored in compact form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Isni {
+    bytes: [u8; LENGTH],
+}
+
+impl Isni {
+    /// Parse and validate an ISNI or ORCID iD in any of the accepted forms.
+    pub fn parse(isni: &str) -> Result<Isni, IsniError> {
+        let mut bytes = [0u8; LENGTH];
+        compact(isni, &mut bytes)?;
+
+        let (base, check) = bytes.split_at(BASE_LENGTH);
+        check_base(base)?;
+        let found = check[0];
+        if !(found.is_ascii_digit() || found == b'X') {
+            return Err(IsniError::IllegalCharacter {
+                position: BASE_LENGTH,
+                byte: found,
+            });
+        }
+
+        if MOD_11_2.validate_ascii_bytes(&bytes) {
+            Ok(Isni { bytes })
+        } else {
+            Err(IsniError::CheckMismatch {
+                expected: check_char(&MOD_11_2, base.iter().copied()),
+                found,
+            })
+        }
+    }
+
+    /// Complete a base of fifteen digits (in any of the accepted forms) with its check character.
+    pub fn generate(base: &str) -> Result<Isni, IsniError> {
+        let mut bytes = [0u8; LENGTH];
+        compact(base, &mut bytes[..BASE_LENGTH])?;
+        check_base(&bytes[..BASE_LENGTH])?;
+        bytes[BASE_LENGTH] = check_char(&MOD_11_2, bytes[..BASE_LENGTH].iter().copied());
+        Ok(Isni { bytes })
+    }
+
+    /// The compact form, such as "0000000218250097".
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes).expect("ISNI is ASCII")
+    }
+
+    /// The check character: a digit or 'X'.
+    pub fn check_character(&self) -> char {
+        char::from(self.bytes[BASE_LENGTH])
+    }
+
+    /// Write the identifier in groups of four separated by `separator`.
+    pub fn write_grouped(&self, f: &mut dyn fmt::Write, separator: char) -> fmt::Result {
+        for (i, &c) in self.bytes.iter().enumerate() {
+            if i > 0 && i % 4 == 0 {
+                f.write_char(separator)?;
+            }
+            f.write_char(char::from(c))?;
+        }
+        Ok(())
+    }
+
+    /// The hyphenated form, such as "0000-0002-1825-0097".
+    #[cfg(feature = "alloc")]
+    pub fn to_hyphenated(&self) -> String {
+        let mut s = String::with_capacity(LENGTH + 3);
+        self.write_grouped(&mut s, '-')
+            .expect("writing to a String cannot fail");
+        s
+    }
+
+    /// The spaced form, such as "0000 0002 1825 0097".
+    #[cfg(feature = "alloc")]
+    pub fn to_spaced(&self) -> String {
+        let mut s = String::with_capacity(LENGTH + 3);
+        self.write_grouped(&mut s, ' ')
+            .expect("writing to a String cannot fail");
+        s
+    }
+
+    /// The ORCID URL, such as `https://orcid.org/0000-0002-1825-0097`.
+    #[cfg(feature = "alloc")]
+    pub fn to_orcid_url(&self) -> String {
+        let mut s = String::from(URL_PREFIXES[0]);
+        s.push_str(&self.to_hyphenated());
+        s
+    }
+}
+
+/// Formats the identifier in its canonical hyphenated form, such as "0000-0002-1825-0097".
+impl fmt::Display for Isni {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_grouped(f, '-')
+    }
+}
+
+impl core::str::FromStr for Isni {
+    type Err = IsniError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Isni::parse(s)
+    }
+}
+
+/// Validate an ISNI or ORCID iD in any of the accepted forms, reporting the reason if it is not
+/// valid.
+pub fn validate(isni: &str) -> Result<(), IsniError> {
+    Isni::parse(isni).map(|_| ())
+}
+
+/// Returns true if the ISNI or ORCID iD is valid.
+pub fn is_valid(isni: &str) -> bool {
+    validate(isni).is_ok()
+}
+
+/// Compute the check character (a digit or 'X') for a base of fifteen digits, in any of the
+/// accepted forms.
+pub fn check_character(base: &str) -> Result<char, IsniError> {
+    Isni::generate(base).map(|isni| isni.check_character())
+}
+
+/// Reformat a valid ISNI or ORCID iD in its canonical hyphenated form.
+#[cfg(feature = "alloc")]
+pub fn to_canonical(isni: &str) -> Result<String, IsniError> {
+    Isni::parse(isni).map(|isni| isni.to_hyphenated())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::isni::*;
+
+    const EXAMPLES: [&str; 5] = [
+        "0000000218250097",
+        "000000021694233X",
+        "000000012281955X",
+        "0000000121032683",
+        "0000000219090371",
+    ];
+
+    #[test]
+    fn validate_examples() {
+        for example in EXAMPLES {
+            assert_eq!(Ok(()), validate(example), "{}", example);
+            assert_eq!(
+                Ok(char::from(example.as_bytes()[BASE_LENGTH])),
+                check_character(&example[..BASE_LENGTH])
+            );
+        }
+    }
+
+    #[test]
+    fn parse_accepts_all_forms() {
+        let expected = Isni::parse("000000021694233X").unwrap();
+        for input in [
+            "0000-0002-1694-233X",
+            "0000 0002 1694 233x",
+            "https://orcid.org/0000-0002-1694-233X",
+            "http://orcid.org/0000-0002-1694-233X",
+            "https://isni.org/isni/000000021694233X",
+            "https://www.orcid.org/0000-0002-1694-233X",
+            "HTTPS://ORCID.ORG/0000-0002-1694-233X",
+            "Https://WWW.ISNI.org/isni/000000021694233X",
+            " 0000-0002-1694-233X\n",
+        ] {
+            assert_eq!(Ok(expected), input.parse(), "{}", input);
+        }
+        assert_eq!('X', expected.check_character());
+        assert_eq!("000000021694233X", expected.as_str());
+    }
+
+    #[test]
+    fn validate_reports_reasons() {
+        assert_eq!(
+            Err(IsniError::CheckMismatch {
+                expected: b'7',
+                found: b'8'
+            }),
+            validate("0000-0002-1825-0098")
+        );
+        assert_eq!(
+            Err(IsniError::WrongLength {
+                expected: 16,
+                length: 15
+            }),
+            validate("0000-0002-1825-009")
+        );
+        assert_eq!(
+            Err(IsniError::IllegalCharacter {
+                position: 4,
+                byte: b'X'
+            }),
+            validate("0000X00218250097")
+        );
+        assert_eq!(
+            Err(IsniError::IllegalCharacter {
+                position: 15,
+                byte: b'.'
+            }),
+            validate("000000021825009.")
+        );
+        assert_eq!(
+            Err(IsniError::WrongLength {
+                expected: 16,
+                length: 32
+            }),
+            validate("ftp://orcid.org/0000-0002-1825-0097")
+        );
+    }
+
+    #[test]
+    fn generate_completes_base() {
+        let isni = Isni::generate("0000-0002-1825-009").unwrap();
+        assert_eq!("0000000218250097", isni.as_str());
+        assert_eq!(
+            Err(IsniError::WrongLength {
+                expected: 15,
+                length: 16
+            }),
+            Isni::generate("0000000218250097")
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn format_canonically() {
+        use alloc::string::ToString;
+
+        let isni = Isni::parse("https://orcid.org/0000-0002-1825-0097").unwrap();
+        assert_eq!("0000-0002-1825-0097", isni.to_string());
+        assert_eq!("0000-0002-1825-0097", isni.to_hyphenated());
+        assert_eq!("0000 0002 1825 0097", isni.to_spaced());
+        assert_eq!("https://orcid.org/0000-0002-1825-0097", isni.to_orcid_url());
+        assert_eq!(
+            Ok("0000-0002-1694-233X"),
+            to_canonical("000000021694233x").as_deref()
+        );
+    }
+}
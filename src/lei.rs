@@ -0,0 +1,355 @@
+//! # iso_iec_7064::lei
+//!
+//! Legal Entity Identifiers (LEI), as specified by ISO 17442-1:2020, built on `MOD_97_10`.
+//!
+//! An LEI is 20 characters long: a four-character prefix identifying the Local Operating Unit
+//! (LOU) that issued it, a fourteen-character entity-specific part, and two check digits. The
+//! first eighteen characters are upper-case letters and digits. The check digits are computed with
+//! MOD 97-10 over the first eighteen characters, with each letter expanded to two decimal digits
+//! (A = 10, ..., Z = 35), following the same "98 minus" convention as IBAN, so they are always in
+//! the range 02 to 98.
+//!
+//! The functions here accept LEIs in either case, with spaces separating groups of characters
+//! and surrounding whitespace. Positions reported in errors refer to the compact form.
+
+use core::fmt;
+
+use crate::check_digits;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// The length of an LEI.
+pub const LENGTH: usize = 20;
+
+/// The length of the LOU prefix.
+pub const PREFIX_LENGTH: usize = 4;
+
+/// The length of the entity-specific part.
+pub const ENTITY_SPECIFIC_LENGTH: usize = 14;
+
+/// The length of the base of an LEI: everything before the check digits.
+pub const BASE_LENGTH: usize = PREFIX_LENGTH + ENTITY_SPECIFIC_LENGTH;
+
+/// The name of LEIs in error messages.
+const NAME: &str = "LEI";
+
+/// The reason an LEI was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LeiError {
+    /// The input does not have the expected length once spaces are removed.
+    WrongLength { expected: usize, length: usize },
+    /// The LOU prefix given to `generate` does not have the expected length once spaces are
+    /// removed.
+    WrongPrefixLength { expected: usize, length: usize },
+    /// The entity-specific part given to `generate` does not have the expected length once spaces
+    /// are removed.
+    WrongEntitySpecificLength { expected: usize, length: usize },
+    /// A character other than a letter or digit was encountered at the given (zero-based)
+    /// position, or a non-digit was encountered in the check digits.
+    IllegalCharacter { position: usize, byte: u8 },
+    /// The check digits are not in the range 02 to 98.
+    InvalidCheckDigits,
+    /// The LEI is well-formed, but the check digits do not satisfy the check.
+    CheckMismatch { expected: u8, found: u8 },
+}
+
+impl fmt::Display for LeiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeiError::WrongLength { expected, length } => {
+                write!(f, "LEI length {} should be {}", length, expected)
+            }
+            LeiError::WrongPrefixLength { expected, length } => {
+                write!(f, "LEI prefix length {} should be {}", length, expected)
+            }
+            LeiError::WrongEntitySpecificLength { expected, length } => write!(
+                f,
+                "LEI entity-specific part length {} should be {}",
+                length, expected
+            ),
+            LeiError::IllegalCharacter { position, byte } => {
+                check_digits::write_illegal_character(f, NAME, *position, *byte)
+            }
+            LeiError::InvalidCheckDigits => check_digits::write_invalid(f, NAME),
+            LeiError::CheckMismatch { expected, found } => {
+                check_digits::write_mismatch(f, NAME, *expected, *found)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LeiError {}
+
+/// Normalize the input into the buffer, failing unless it fills the buffer exactly.
+fn compact<'a>(input: &str, buffer: &'a mut [u8]) -> Result<&'a [u8], LeiError> {
+    let length = check_digits::ELECTRONIC.normalize_into(input.trim().as_bytes(), buffer);
+    if length != buffer.len() {
+        return Err(LeiError::WrongLength {
+            expected: buffer.len(),
+            length,
+        });
+    }
+    Ok(buffer)
+}
+
+/// Check that every byte of the base is an upper-case letter or digit.
+fn check_base(bytes: &[u8]) -> Result<(), LeiError> {
+    match bytes
+        .iter()
+        .position(|c| !(c.is_ascii_digit() || c.is_ascii_uppercase()))
+    {
+        Some(i) => Err(LeiError::IllegalCharacter {
+            position: i,
+            byte: bytes[i],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// A valid LEI, stored in compact upper-case form, giving access to its parts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Lei {
+    bytes: [u8; LENGTH],
+}
+
+impl Lei {
+    /// Parse and validate an LEI.
+    pub fn parse(lei: &str) -> Result<Lei, LeiError> {
+        let mut bytes = [0u8; LENGTH];
+        compact(lei, &mut bytes)?;
+
+        let (base, check) = bytes.split_at(BASE_LENGTH);
+        check_base(base)?;
+        if let Some(i) = check.iter().position(|c| !c.is_ascii_digit()) {
+            return Err(LeiError::IllegalCharacter {
+                position: BASE_LENGTH + i,
+                byte: check[i],
+            });
+        }
+        let found = check_digits::parse(check).ok_or(LeiError::InvalidCheckDigits)?;
+
+        let expected = check_digits::compute(&[], base);
+        if expected == found {
+            Ok(Lei { bytes })
+        } else {
+            Err(LeiError::CheckMismatch { expected, found })
+        }
+    }
+
+    /// Complete an eighteen-character base with its check digits.
+    pub fn generate(base: &str) -> Result<Lei, LeiError> {
+        let mut bytes = [0u8; LENGTH];
+        compact(base, &mut bytes[..BASE_LENGTH])?;
+        Lei::complete(bytes)
+    }
+
+    /// Fill in the check digits after the base, which is already in the first eighteen bytes.
+    fn complete(mut bytes: [u8; LENGTH]) -> Result<Lei, LeiError> {
+        let (base, check) = bytes.split_at_mut(BASE_LENGTH);
+        check_base(base)?;
+        let value = check_digits::compute(&[], base);
+        check[0] = b'0' + value / 10;
+        check[1] = b'0' + value % 10;
+        Ok(Lei { bytes })
+    }
+
+    /// The complete LEI, such as "5493001KJTIIGC8Y1R12".
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes).expect("LEI is ASCII")
+    }
+
+    /// The four-character prefix identifying the issuing Local Operating Unit.
+    pub fn lou_prefix(&self) -> &str {
+        &self.as_str()[..PREFIX_LENGTH]
+    }
+
+    /// The fourteen-character entity-specific part.
+    pub fn entity_specific(&self) -> &str {
+        &self.as_str()[PREFIX_LENGTH..BASE_LENGTH]
+    }
+
+    /// The two check digits, as a number from 2 to 98.
+    pub fn check_digits(&self) -> u8 {
+        let check = &self.bytes[BASE_LENGTH..];
+        (check[0] - b'0') * 10 + (check[1] - b'0')
+    }
+}
+
+impl fmt::Display for Lei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl core::str::FromStr for Lei {
+    type Err = LeiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lei::parse(s)
+    }
+}
+
+/// Validate an LEI, reporting the reason if it is not valid.
+pub fn validate(lei: &str) -> Result<(), LeiError> {
+    Lei::parse(lei).map(|_| ())
+}
+
+/// Returns true if the LEI is valid.
+pub fn is_valid(lei: &str) -> bool {
+    validate(lei).is_ok()
+}
+
+/// Compute the check digits (02 to 98) for the eighteen-character base of an LEI.
+pub fn check_digits(base: &str) -> Result<u8, LeiError> {
+    Lei::generate(base).map(|lei| lei.check_digits())
+}
+
+/// Generate a complete LEI from a four-character LOU prefix and a fourteen-character
+/// entity-specific part. Positions reported in errors refer to the complete LEI.
+#[cfg(feature = "alloc")]
+pub fn generate(lou_prefix: &str, entity_specific: &str) -> Result<String, LeiError> {
+    let mut bytes = [0u8; LENGTH];
+    if let Err(LeiError::WrongLength { expected, length }) =
+        compact(lou_prefix, &mut bytes[..PREFIX_LENGTH])
+    {
+        return Err(LeiError::WrongPrefixLength { expected, length });
+    }
+    if let Err(LeiError::WrongLength { expected, length }) =
+        compact(entity_specific, &mut bytes[PREFIX_LENGTH..BASE_LENGTH])
+    {
+        return Err(LeiError::WrongEntitySpecificLength { expected, length });
+    }
+    Ok(String::from(Lei::complete(bytes)?.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lei::*;
+
+    const EXAMPLES: [&str; 6] = [
+        "5493001KJTIIGC8Y1R12",
+        "HWUPKR0MPOU8FGXBT394",
+        "7ZW8QJWVPR4P1J1KQY45",
+        "529900T8BM49AURSDO55",
+        "213800WAVVOPS85N2205",
+        "YZ83GD8L7GG84979J516",
+    ];
+
+    #[test]
+    fn validate_examples() {
+        for example in EXAMPLES {
+            assert_eq!(Ok(()), validate(example), "{}", example);
+            assert_eq!(
+                Ok(example[BASE_LENGTH..].parse().unwrap()),
+                check_digits(&example[..BASE_LENGTH])
+            );
+        }
+    }
+
+    #[test]
+    fn validate_reports_reasons() {
+        assert_eq!(
+            Err(LeiError::CheckMismatch {
+                expected: 94,
+                found: 95
+            }),
+            validate("HWUPKR0MPOU8FGXBT395")
+        );
+        assert_eq!(
+            Err(LeiError::WrongLength {
+                expected: 20,
+                length: 19
+            }),
+            validate("HWUPKR0MPOU8FGXBT39")
+        );
+        assert_eq!(
+            Err(LeiError::IllegalCharacter {
+                position: 2,
+                byte: b'-'
+            }),
+            validate("HW-PKR0MPOU8FGXBT394")
+        );
+        assert_eq!(
+            Err(LeiError::IllegalCharacter {
+                position: 19,
+                byte: b'A'
+            }),
+            validate("HWUPKR0MPOU8FGXBT39A")
+        );
+        assert_eq!(
+            Err(LeiError::InvalidCheckDigits),
+            validate("HWUPKR0MPOU8FGXBT399")
+        );
+    }
+
+    #[test]
+    fn parse_exposes_parts() {
+        let lei = Lei::parse("5493001KJTIIGC8Y1R12").unwrap();
+        assert_eq!("5493", lei.lou_prefix());
+        assert_eq!("001KJTIIGC8Y1R", lei.entity_specific());
+        assert_eq!(12, lei.check_digits());
+    }
+
+    #[test]
+    fn parse_normalizes() {
+        let expected = Lei::parse("5493001KJTIIGC8Y1R12").unwrap();
+        for input in [
+            "5493001kjtiigc8y1r12",
+            "5493 001K JTII GC8Y 1R12",
+            " 5493001KJTIIGC8Y1R12\n",
+        ] {
+            assert_eq!(Ok(expected), input.parse(), "{}", input);
+        }
+        assert_eq!("5493001KJTIIGC8Y1R12", expected.as_str());
+        assert_eq!(Ok(expected), Lei::generate("5493 001k jtii gc8y 1r"));
+        assert_eq!(Ok(12), check_digits("5493001kjtiigc8y1r"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn generate_examples() {
+        for example in EXAMPLES {
+            assert_eq!(
+                Ok(example),
+                generate(&example[..4], &example[4..18]).as_deref()
+            );
+        }
+        // Check digits 213800WAVVOPS85N22 need a leading zero.
+        assert_eq!(
+            Ok("213800WAVVOPS85N2205"),
+            generate("2138", "00WAVVOPS85N22").as_deref()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn generate_reports_reasons_by_part() {
+        use alloc::string::ToString;
+
+        let error = generate("549", "3001KJTIIGC8Y1R").unwrap_err();
+        assert_eq!(
+            LeiError::WrongPrefixLength {
+                expected: 4,
+                length: 3
+            },
+            error
+        );
+        assert_eq!("LEI prefix length 3 should be 4", error.to_string());
+        assert_eq!(
+            Err(LeiError::WrongEntitySpecificLength {
+                expected: 14,
+                length: 15
+            }),
+            generate("5493", "001KJTIIGC8Y1R1")
+        );
+        assert_eq!(
+            Err(LeiError::IllegalCharacter {
+                position: 6,
+                byte: b'-'
+            }),
+            generate("5493", "00-KJTIIGC8Y1R")
+        );
+    }
+}
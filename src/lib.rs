@@ -7,17 +7,128 @@
 //! This crate implements all the _Check Character Systems_ specified in The Standard.
 //!
 //! Note that the `Mod97_10` system is used by [ISO 17442-1:2020](https://www.iso.org/standard/78829.html)
-//! "Financial services — Legal entity identifier (LEI) — Part 1: Assignment", which is implemented
-//! by [the `lei` crate](https://crates.io/crates/lei).
+//! "Financial services — Legal entity identifier (LEI) — Part 1: Assignment", for which the `lei`
+//! module provides validation and generation. A more complete implementation is provided by
+//! [the `lei` crate](https://crates.io/crates/lei).
+//!
+//! ## Cargo features
+//!
+//! The crate is `no_std` compatible, and all validation and checksum operations work on ASCII
+//! bytes without allocating. The following features are enabled by default:
+//!
+//! * `alloc`: APIs producing owned strings, such as `System::complete_string`.
+//! * `std`: implementations of `std::error::Error` for the error types. Implies `alloc`.
+//!
+//! The optional `serde` feature (which implies `alloc`) adds serialization of validated
+//! identifiers and system selections; see the `serde` module. The optional `cli` feature builds
+//! the `iso7064` command-line tool for validating identifiers and computing check characters.
+//!
+//! Use `default-features = false` to build for targets without `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// Assert at compile time that a string literal (or other `const` string) satisfies the check of
+/// a _Check Character System_, failing compilation if it does not. This is useful for hard-coded
+/// identifiers in fixtures and configuration constants.
+///
+/// ```
+/// use iso_iec_7064::{const_assert_valid, MOD_11_2, MOD_97_10};
+///
+/// const_assert_valid!(MOD_11_2, "079X");
+/// const_assert_valid!(MOD_97_10, "79444");
+/// ```
+///
+/// ```compile_fail
+/// use iso_iec_7064::{const_assert_valid, MOD_97_10};
+///
+/// const_assert_valid!(MOD_97_10, "79445");
+/// ```
+#[macro_export]
+macro_rules! const_assert_valid {
+    ($system:expr, $string:expr $(,)?) => {
+        const _: () = assert!(
+            $system.validate_ascii_bytes($string.as_bytes()),
+            concat!(
+                "invalid check character(s) in ",
+                stringify!($string),
+                " for ",
+                stringify!($system)
+            )
+        );
+    };
+}
+
+#[cfg(feature = "alloc")]
+pub mod analysis;
+
+pub mod alphabet;
+pub use alphabet::Alphabet;
+
+mod check_digits;
+
+#[cfg(feature = "alloc")]
+pub mod checked;
+#[cfg(feature = "alloc")]
+pub use checked::Checked;
+
+pub mod cn_resident_id;
+
+#[cfg(feature = "alloc")]
+pub mod correction;
+#[cfg(feature = "alloc")]
+pub use correction::{Correction, CorrectionKind};
+
+pub mod dyn_system;
+pub use dyn_system::DynSystem;
+
+pub mod error;
+pub use error::{Error, LookupError};
+
+pub mod expansion;
+pub use expansion::{AlphanumericExpansion, MOD_97_10_ALPHANUMERIC};
+
+pub mod grid;
 
 pub mod hybrid;
-pub use hybrid::HybridSystem;
+pub use hybrid::{HybridHasher, HybridSystem};
+
+pub mod iban;
+
+pub mod identifier;
+pub use identifier::Identifier;
+
+pub mod isan;
+
+pub mod isni;
+
+pub mod lei;
+
+pub mod national_id;
+
+pub mod normalize;
+#[cfg(feature = "alloc")]
+pub use normalize::Normalized;
+pub use normalize::Normalizer;
 
 pub mod pure;
-pub use pure::PureSystem;
+pub use pure::{PureHasher, PureSystem};
+
+#[cfg(feature = "alloc")]
+pub mod random;
+
+pub mod rf_reference;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+pub mod standard;
+pub use standard::StandardSystem;
 
 pub mod system;
-pub use system::System;
+pub use system::{CheckChars, System};
 
 // The _Pure_ _Check Character Systems_ defined in The Standard.
 
@@ -46,3 +157,117 @@ pub use mod_27_26::MOD_27_26;
 
 mod mod_37_36;
 pub use mod_37_36::MOD_37_36;
+
+// The registry of _Check Character Systems_ defined in The Standard.
+
+/// All the _Check Character Systems_ defined in The Standard, in order of designation.
+pub const SYSTEMS: [StandardSystem; 8] = StandardSystem::ALL;
+
+/// Look up a _Check Character System_ by its designation from The Standard, Section 5.4.2, Table
+/// 3 "Single digit designations". Designation zero is reserved for "No check character or
+/// non-standard system", and is reported as `LookupError::NoCheck`.
+pub fn system_by_designation(designation: u8) -> Result<StandardSystem, LookupError> {
+    match designation {
+        0 => Err(LookupError::NoCheck),
+        1..=8 => Ok(SYSTEMS[(designation - 1) as usize]),
+        _ => Err(LookupError::UnknownDesignation(designation.into())),
+    }
+}
+
+/// Look up a _Check Character System_ by name. In addition to the canonical names (such as
+/// "ISO/IEC 7064, MOD 97-10"), this accepts short aliases without the "ISO/IEC 7064" prefix, in
+/// any case, and with any of '-', ',', '_', '/' or ' ' between the numbers, so "MOD 97-10",
+/// "mod97-10" and "Mod97_10" all resolve to `MOD_97_10`.
+pub fn system_by_name(name: &str) -> Result<StandardSystem, LookupError> {
+    let numbers = mod_numbers(name).ok_or(LookupError::UnknownName)?;
+    SYSTEMS
+        .iter()
+        .find(|system| mod_numbers(system.system_name()) == Some(numbers))
+        .copied()
+        .ok_or(LookupError::UnknownName)
+}
+
+/// Extract the pair of numbers following "MOD" in a system name, such as (97, 10) for
+/// "ISO/IEC 7064, MOD 97-10" or (11, 10) for "mod 11,10".
+fn mod_numbers(name: &str) -> Option<(u16, u16)> {
+    fn split_number(s: &str) -> Option<(u16, &str)> {
+        let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let number = s[..end].parse().ok()?;
+        Some((number, &s[end..]))
+    }
+
+    let mut rest = name.trim();
+    if let Some(r) = normalize::strip_prefix_ignore_case(rest, "ISO/IEC 7064") {
+        rest = r.trim_start_matches([',', ':', ' ']);
+    }
+    rest = normalize::strip_prefix_ignore_case(rest, "MOD")?.trim_start_matches([' ', '_']);
+
+    let (modulus, rest) = split_number(rest)?;
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix(['-', ',', '_', '/', ' '])?.trim_start();
+    let (radix, rest) = split_number(rest)?;
+
+    if rest.trim().is_empty() {
+        Some((modulus, radix))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn lookup_by_designation() {
+        assert_eq!(Ok(StandardSystem::Mod11_2), system_by_designation(1));
+        assert_eq!(Ok(StandardSystem::Mod37_36), system_by_designation(8));
+        assert_eq!(Err(LookupError::NoCheck), system_by_designation(0));
+        assert_eq!(
+            Err(LookupError::UnknownDesignation(9)),
+            system_by_designation(9)
+        );
+    }
+
+    #[test]
+    fn lookup_by_canonical_name() {
+        for system in SYSTEMS {
+            assert_eq!(Ok(system), system_by_name(system.system_name()));
+        }
+    }
+
+    #[test]
+    fn lookup_by_alias() {
+        assert_eq!(Ok(StandardSystem::Mod97_10), system_by_name("MOD 97-10"));
+        assert_eq!(Ok(StandardSystem::Mod97_10), system_by_name("mod97-10"));
+        assert_eq!(Ok(StandardSystem::Mod97_10), system_by_name("Mod97_10"));
+        assert_eq!(Ok(StandardSystem::Mod11_10), system_by_name("MOD 11,10"));
+        assert_eq!(Ok(StandardSystem::Mod11_10), system_by_name("mod 11-10"));
+        assert_eq!(
+            Ok(StandardSystem::Mod1271_36),
+            system_by_name("iso/iec 7064 mod 1271-36")
+        );
+        assert_eq!(Err(LookupError::UnknownName), system_by_name("MOD 97"));
+        assert_eq!(Err(LookupError::UnknownName), system_by_name("MOD 10-97"));
+        assert_eq!(Err(LookupError::UnknownName), system_by_name("Luhn"));
+    }
+
+    #[test]
+    fn from_str_accepts_designations_and_names() {
+        assert_eq!(Ok(StandardSystem::Mod97_10), "3".parse());
+        assert_eq!(Ok(StandardSystem::Mod27_26), "MOD 27,26".parse());
+        assert_eq!(Err(LookupError::NoCheck), "0".parse::<StandardSystem>());
+        assert_eq!(
+            Err(LookupError::UnknownDesignation(9)),
+            "9".parse::<StandardSystem>()
+        );
+        assert_eq!(
+            Err(LookupError::UnknownDesignation(300)),
+            "300".parse::<StandardSystem>()
+        );
+        assert_eq!(
+            Err(LookupError::UnknownDesignation(u32::MAX)),
+            "99999999999".parse::<StandardSystem>()
+        );
+    }
+}
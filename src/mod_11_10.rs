@@ -1,17 +1,17 @@
 use crate::hybrid::HybridSystem;
-// use crate::Alphabet;
+use crate::Alphabet;
 
 pub const MOD_11_10: HybridSystem<9> = HybridSystem {
     name: "ISO/IEC 7064, MOD 11,10",
     designation: 6,
-    // alphabet: Alphabet::Numeric,
+    alphabet: Alphabet::Numeric,
     modulus: 10,
 };
 
 #[cfg(test)]
 mod tests {
     use crate::mod_11_10::MOD_11_10;
-    use crate::System;
+    use crate::{CheckChars, Error, System};
 
     // Test case from The Standard, Section 10.1.2 "Example"
     #[test]
@@ -54,4 +54,57 @@ mod tests {
         assert_eq!(Some(8), MOD_11_10.checksum_string("0798"));
         assert_eq!(Some(6), MOD_11_10.checksum_string("0799"));
     }
+
+    #[test]
+    fn try_validate_reports_reasons() {
+        assert_eq!(Ok(()), MOD_11_10.try_validate_string("07945"));
+        assert_eq!(
+            Err(Error::CheckMismatch {
+                expected: CheckChars::from_ascii_bytes(b"5").unwrap(),
+                found: CheckChars::from_ascii_bytes(b"6").unwrap(),
+            }),
+            MOD_11_10.try_validate_string("07946")
+        );
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 4,
+                byte: b'X'
+            }),
+            MOD_11_10.try_validate_string("0794X")
+        );
+    }
+
+    #[test]
+    fn rejects_empty_payload() {
+        assert!(!MOD_11_10.validate_string("1"));
+        assert_eq!(None, MOD_11_10.checksum_string(""));
+        assert_eq!(
+            Err(Error::TooShort {
+                minimum: 2,
+                length: 1
+            }),
+            MOD_11_10.try_validate_string("1")
+        );
+    }
+
+    #[test]
+    fn hasher_matches_one_shot_computation() {
+        let mut hasher = MOD_11_10.hasher();
+        assert_eq!(None, hasher.finalize_checksum());
+        hasher.update(b"07");
+        hasher.update(b"94");
+        assert_eq!(Some(5), hasher.finalize_checksum());
+
+        hasher.update(b"5");
+        assert!(hasher.finalize_validate());
+    }
+
+    const CHECKSUM: Option<u16> = MOD_11_10.checksum_ascii_bytes(b"0794");
+    crate::const_assert_valid!(MOD_11_10, "07945");
+
+    #[test]
+    fn const_checksum_example_from_standard() {
+        assert_eq!(Some(5), CHECKSUM);
+        assert!(!MOD_11_10.validate_ascii_bytes(b"07946"));
+    }
 }
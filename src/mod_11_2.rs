@@ -1,10 +1,10 @@
 use crate::pure::PureSystem;
-// use crate::Alphabet;
+use crate::Alphabet;
 
 pub const MOD_11_2: PureSystem<1, 10, 11, 2> = PureSystem {
     name: "ISO/IEC 7064, MOD 11-2",
     designation: 1,
-    // alphabet: Alphabet::NumericWithX,
+    alphabet: Alphabet::NumericWithX,
     // modulus: 11,
     // radix: 2,
 };
@@ -12,7 +12,7 @@ pub const MOD_11_2: PureSystem<1, 10, 11, 2> = PureSystem {
 #[cfg(test)]
 mod tests {
     use crate::mod_11_2::MOD_11_2;
-    use crate::System;
+    use crate::{CheckChars, Error, Normalizer, System};
 
     // Test cases from The Standard, Section 7.1.2 "Example"
     #[test]
@@ -36,4 +36,123 @@ mod tests {
 
         assert_eq!(Some(10), MOD_11_2.checksum_string("079"));
     }
+
+    #[test]
+    fn check_chars_examples_from_standard() {
+        assert_eq!("0", MOD_11_2.check_chars_string("0794").unwrap().as_str());
+        assert_eq!("X", MOD_11_2.check_chars_string("079").unwrap().as_str());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn complete_string_examples_from_standard() {
+        assert_eq!(Some("07940"), MOD_11_2.complete_string("0794").as_deref());
+        assert_eq!(Some("079X"), MOD_11_2.complete_string("079").as_deref());
+    }
+
+    #[test]
+    fn check_chars_rejects_out_of_range_checksum() {
+        assert_eq!(None, MOD_11_2.check_chars(11));
+    }
+
+    #[test]
+    fn try_validate_reports_reasons() {
+        assert_eq!(Ok(()), MOD_11_2.try_validate_string("079X"));
+        assert_eq!(
+            Err(Error::SupplementaryCharacterInPayload {
+                position: 0,
+                byte: b'X'
+            }),
+            MOD_11_2.try_validate_string("X7940")
+        );
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 2,
+                byte: b'a'
+            }),
+            MOD_11_2.try_validate_string("07a40")
+        );
+        assert_eq!(
+            Err(Error::TooShort {
+                minimum: 2,
+                length: 1
+            }),
+            MOD_11_2.try_validate_string("0")
+        );
+        assert_eq!(
+            Err(Error::CheckMismatch {
+                expected: CheckChars::from_ascii_bytes(b"0").unwrap(),
+                found: CheckChars::from_ascii_bytes(b"X").unwrap(),
+            }),
+            MOD_11_2.try_validate_string("0794X")
+        );
+    }
+
+    #[test]
+    fn try_checksum_reports_reasons() {
+        assert_eq!(Ok(10), MOD_11_2.try_checksum_string("079"));
+        assert_eq!(
+            Err(Error::SupplementaryCharacterInPayload {
+                position: 3,
+                byte: b'X'
+            }),
+            MOD_11_2.try_checksum_string("079X")
+        );
+        assert_eq!(
+            Err(Error::TooShort {
+                minimum: 1,
+                length: 0
+            }),
+            MOD_11_2.try_checksum_string("")
+        );
+    }
+
+    #[test]
+    fn hasher_rejects_supplementary_chars_in_payload() {
+        let mut hasher = MOD_11_2.hasher();
+        hasher.update(b"079X");
+        assert!(hasher.finalize_validate());
+        assert_eq!(None, hasher.finalize_checksum());
+    }
+
+    #[test]
+    fn validate_normalized_orcid() {
+        let normalizer = Normalizer::LENIENT;
+        assert!(MOD_11_2.validate_normalized("0000-0002-1825-0097", &normalizer));
+        assert!(MOD_11_2.validate_normalized("0000 0002 1694 233x", &normalizer));
+        assert!(!MOD_11_2.validate_normalized("0000-0002-1825-0097", &Normalizer::NONE));
+        assert!(!MOD_11_2.validate_normalized("0000-0002-1825-0097", &normalizer.strict(true)));
+        assert_eq!(
+            Some(7),
+            MOD_11_2.checksum_normalized("0000-0002-1825-009", &normalizer)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_validate_normalized_reports_original_positions() {
+        let normalizer = Normalizer::LENIENT;
+        assert_eq!(
+            Ok(()),
+            MOD_11_2.try_validate_normalized("0000-0002-1825-0097", &normalizer)
+        );
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 6,
+                byte: b'Q'
+            }),
+            MOD_11_2.try_validate_normalized("0000-0q02-1825-0097", &normalizer)
+        );
+        assert_eq!(
+            Err(Error::StrippedCharacter {
+                position: 4,
+                byte: b'-'
+            }),
+            MOD_11_2.try_validate_normalized("0000-0002-1825-0097", &normalizer.strict(true))
+        );
+        assert_eq!(
+            Ok(7),
+            MOD_11_2.try_checksum_normalized("0000-0002-1825-009", &normalizer)
+        );
+    }
 }
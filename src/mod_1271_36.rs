@@ -1,10 +1,10 @@
 use crate::pure::PureSystem;
-// use crate::Alphabet;
+use crate::Alphabet;
 
 pub const MOD_1271_36: PureSystem<2, 35, 1271, 36> = PureSystem {
     name: "ISO/IEC 7064, MOD 1271-36",
     designation: 5,
-    // alphabet: Alphabet::Alphanumeric,
+    alphabet: Alphabet::Alphanumeric,
     // modulus: 1271,
     // radix: 36,
 };
@@ -12,11 +12,42 @@ pub const MOD_1271_36: PureSystem<2, 35, 1271, 36> = PureSystem {
 #[cfg(test)]
 mod tests {
     use crate::mod_1271_36::MOD_1271_36;
-    use crate::System;
+    use crate::{Normalizer, System};
 
     // Test case from The Standard, Section 8.2 "Example using recursive method"
     #[test]
     fn compute_example_from_standard() {
         assert_eq!(Some(140), MOD_1271_36.checksum_string("ISO79"));
     }
+
+    #[test]
+    fn check_chars_example_from_standard() {
+        assert_eq!("3W", MOD_1271_36.check_chars(140).unwrap().as_str());
+        assert_eq!(
+            "3W",
+            MOD_1271_36.check_chars_string("ISO79").unwrap().as_str()
+        );
+        assert!(MOD_1271_36.validate_string("ISO793W"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn complete_string_example_from_standard() {
+        assert_eq!(
+            Some("ISO793W"),
+            MOD_1271_36.complete_string("ISO79").as_deref()
+        );
+    }
+
+    #[test]
+    fn checksum_normalized_folds_case() {
+        assert_eq!(
+            Some(140),
+            MOD_1271_36.checksum_normalized("iso79", &Normalizer::LENIENT)
+        );
+        assert_eq!(
+            None,
+            MOD_1271_36.checksum_normalized("iso79", &Normalizer::NONE)
+        );
+    }
 }
@@ -1,9 +1,9 @@
 use crate::hybrid::HybridSystem;
-// use crate::Alphabet;
+use crate::Alphabet;
 
 pub const MOD_27_26: HybridSystem<25> = HybridSystem {
     name: "ISO/IEC 7064, MOD 27,26",
     designation: 7,
-    // alphabet: Alphabet::Alphabetic,
+    alphabet: Alphabet::Alphabetic,
     modulus: 26,
 };
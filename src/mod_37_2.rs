@@ -1,10 +1,31 @@
 use crate::pure::PureSystem;
-// use crate::Alphabet;
+use crate::Alphabet;
 
 pub const MOD_37_2: PureSystem<1, 36, 37, 2> = PureSystem {
     name: "ISO/IEC 7064, MOD 37-2",
     designation: 2,
-    // alphabet: Alphabet::AlphanumericWithAsterisk,
+    alphabet: Alphabet::AlphanumericWithAsterisk,
     // modulus: 37,
     // radix: 2,
 };
+
+#[cfg(test)]
+mod tests {
+    use crate::mod_37_2::MOD_37_2;
+    use crate::System;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn complete_string_round_trips() {
+        assert_eq!(
+            Some("G123498654321H"),
+            MOD_37_2.complete_string("G123498654321").as_deref()
+        );
+        assert!(MOD_37_2.validate_string("G123498654321H"));
+    }
+
+    #[test]
+    fn check_chars_renders_supplementary_char() {
+        assert_eq!("*", MOD_37_2.check_chars(36).unwrap().as_str());
+    }
+}
@@ -1,9 +1,43 @@
 use crate::hybrid::HybridSystem;
-// use crate::Alphabet;
+use crate::Alphabet;
 
 pub const MOD_37_36: HybridSystem<35> = HybridSystem {
     name: "ISO/IEC 7064, MOD 37,36",
     designation: 8,
-    // alphabet: Alphabet::Alphanumeric,
+    alphabet: Alphabet::Alphanumeric,
     modulus: 36,
 };
+
+#[cfg(test)]
+mod tests {
+    use crate::mod_37_36::MOD_37_36;
+    use crate::{CheckChars, Error, System};
+
+    // GRid and ISAN examples, which use this system over their payloads.
+    #[test]
+    fn validate_examples() {
+        assert!(MOD_37_36.validate_string("A12425GABC1234002M"));
+        assert!(MOD_37_36.validate_string("00000001894700008"));
+        assert!(MOD_37_36.validate_string("000000018947000000000000D"));
+        assert!(!MOD_37_36.validate_string("A12425GABC1234002N"));
+    }
+
+    #[test]
+    fn checksum_examples() {
+        assert_eq!(Some(22), MOD_37_36.checksum_string("A12425GABC1234002"));
+        assert_eq!(Some(8), MOD_37_36.checksum_string("0000000189470000"));
+        assert_eq!(Some(35), MOD_37_36.checksum_string("000000003A8D0000"));
+        assert_eq!(None, MOD_37_36.checksum_string(""));
+    }
+
+    #[test]
+    fn try_validate_reports_mismatch() {
+        assert_eq!(
+            Err(Error::CheckMismatch {
+                expected: CheckChars::from_ascii_bytes(b"M").unwrap(),
+                found: CheckChars::from_ascii_bytes(b"N").unwrap(),
+            }),
+            MOD_37_36.try_validate_string("A12425GABC1234002N")
+        );
+    }
+}
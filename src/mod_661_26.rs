@@ -1,10 +1,34 @@
 use crate::pure::PureSystem;
-// use crate::Alphabet;
+use crate::Alphabet;
 
 pub const MOD_661_26: PureSystem<2, 25, 661, 26> = PureSystem {
     name: "ISO/IEC 7064, MOD 661-26",
     designation: 4,
-    // alphabet: Alphabet::Alphabetic,
+    alphabet: Alphabet::Alphabetic,
     // modulus: 661,
     // radix: 26,
 };
+
+#[cfg(test)]
+mod tests {
+    use crate::mod_661_26::MOD_661_26;
+    use crate::System;
+
+    #[test]
+    fn check_chars_round_trip() {
+        assert_eq!(
+            "RV",
+            MOD_661_26.check_chars_string("ABCDEF").unwrap().as_str()
+        );
+        assert!(MOD_661_26.validate_string("ABCDEFRV"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn complete_string_round_trips() {
+        assert_eq!(
+            Some("ABCDEFRV"),
+            MOD_661_26.complete_string("ABCDEF").as_deref()
+        );
+    }
+}
@@ -1,10 +1,10 @@
 use crate::pure::PureSystem;
-// use crate::Alphabet;
+use crate::Alphabet;
 
 pub const MOD_97_10: PureSystem<2, 9, 97, 10> = PureSystem {
     name: "ISO/IEC 7064, MOD 97-10",
     designation: 3,
-    // alphabet: Alphabet::Numeric,
+    alphabet: Alphabet::Numeric,
     // modulus: 97,
     // radix: 10,
 };
@@ -12,11 +12,75 @@ pub const MOD_97_10: PureSystem<2, 9, 97, 10> = PureSystem {
 #[cfg(test)]
 mod tests {
     use crate::mod_97_10::MOD_97_10;
-    use crate::System;
+    use crate::{CheckChars, Error, System};
 
     // Test case from The Standard, Section 8.4 "Simplified procedure for ISO/IEC 7064, MOD 97-10"
     #[test]
     fn checksum_example_from_standard() {
         assert_eq!(Some(44), MOD_97_10.checksum_string("794"));
     }
+
+    #[test]
+    fn check_chars_example_from_standard() {
+        assert_eq!("44", MOD_97_10.check_chars_string("794").unwrap().as_str());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn complete_string_example_from_standard() {
+        assert_eq!(Some("79444"), MOD_97_10.complete_string("794").as_deref());
+    }
+
+    #[test]
+    fn check_chars_pads_single_digit_checksums() {
+        assert_eq!("05", MOD_97_10.check_chars(5).unwrap().as_str());
+    }
+
+    #[test]
+    fn try_validate_reports_expected_check_chars() {
+        assert_eq!(Ok(()), MOD_97_10.try_validate_string("79444"));
+        assert_eq!(
+            Err(Error::CheckMismatch {
+                expected: CheckChars::from_ascii_bytes(b"44").unwrap(),
+                found: CheckChars::from_ascii_bytes(b"45").unwrap(),
+            }),
+            MOD_97_10.try_validate_string("79445")
+        );
+    }
+
+    #[test]
+    fn hasher_matches_one_shot_computation() {
+        let mut hasher = MOD_97_10.hasher();
+        hasher.update(b"7");
+        hasher.update(b"");
+        hasher.update(b"94");
+        assert_eq!(Some(44), hasher.finalize_checksum());
+        assert!(!hasher.finalize_validate());
+
+        let mut valid = hasher.clone();
+        valid.update(b"44");
+        assert!(valid.finalize_validate());
+
+        let mut invalid = hasher;
+        invalid.update(b"45");
+        assert!(!invalid.finalize_validate());
+    }
+
+    #[test]
+    fn hasher_rejects_illegal_chars() {
+        let mut hasher = MOD_97_10.hasher();
+        hasher.update(b"79A");
+        hasher.update(b"444");
+        assert_eq!(None, hasher.finalize_checksum());
+        assert!(!hasher.finalize_validate());
+    }
+
+    const CHECKSUM: Option<u16> = MOD_97_10.checksum_ascii_bytes(b"794");
+    crate::const_assert_valid!(MOD_97_10, "79444");
+
+    #[test]
+    fn const_checksum_example_from_standard() {
+        assert_eq!(Some(44), CHECKSUM);
+        assert!(!MOD_97_10.validate_ascii_bytes(b"79445"));
+    }
 }
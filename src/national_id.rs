@@ -0,0 +1,214 @@
+//! # iso_iec_7064::national_id
+//!
+//! National tax and identification numbers built on `MOD_11_10`.
+//!
+//! Each profile is an `Identifier` describing one national number: its country prefix, the number
+//! of digits before the check digit, how to group the digits for display, and any structural rule
+//! beyond the check digit. The profiles provided are:
+//!
+//! * `OIB`: the Croatian personal identification number (Osobni identifikacijski broj).
+//! * `STEUER_ID`: the German tax identification number (steuerliche Identifikationsnummer).
+//! * `UST_ID`: the German VAT identification number (Umsatzsteuer-Identifikationsnummer).
+//!
+//! Input may include the country prefix (in either case), spaces, hyphens and dots. Positions
+//! reported in errors refer to the normalized input, including any prefix.
+
+use crate::alphabet::Alphabet;
+use crate::identifier::{CheckConvention, CheckPosition, Identifier, PrefixCheck, PrefixMode};
+use crate::normalize::Normalizer;
+use crate::MOD_11_10;
+
+/// The Croatian personal identification number (OIB): eleven digits. The country prefix "HR" of
+/// the VAT form is accepted, but is not part of the OIB.
+pub const OIB: Identifier = Identifier {
+    name: "OIB",
+    system: &MOD_11_10,
+    check_convention: CheckConvention::System,
+    payload_alphabet: Alphabet::Numeric,
+    min_length: 10,
+    max_length: 10,
+    prefix: "HR",
+    prefix_mode: PrefixMode::Dropped,
+    prefix_check: PrefixCheck::Excluded,
+    check_position: CheckPosition::Trailing,
+    normalizer: Normalizer::LENIENT,
+    grouping: &[],
+    separator: ' ',
+    rule: None,
+};
+
+/// The German tax identification number (Steuer-IdNr): eleven digits, the first of which is not
+/// zero, with exactly one digit repeated (twice, or three times but not consecutively) among the
+/// first ten.
+pub const STEUER_ID: Identifier = Identifier {
+    name: "Steuer-IdNr",
+    system: &MOD_11_10,
+    check_convention: CheckConvention::System,
+    payload_alphabet: Alphabet::Numeric,
+    min_length: 10,
+    max_length: 10,
+    prefix: "",
+    prefix_mode: PrefixMode::Required,
+    prefix_check: PrefixCheck::Excluded,
+    check_position: CheckPosition::Trailing,
+    normalizer: Normalizer::LENIENT,
+    grouping: &[2, 3],
+    separator: ' ',
+    rule: Some(steuer_id_rule),
+};
+
+/// The German VAT identification number (USt-IdNr): "DE" followed by nine digits. The prefix may
+/// be omitted from input.
+pub const UST_ID: Identifier = Identifier {
+    name: "USt-IdNr",
+    system: &MOD_11_10,
+    check_convention: CheckConvention::System,
+    payload_alphabet: Alphabet::Numeric,
+    min_length: 8,
+    max_length: 8,
+    prefix: "DE",
+    prefix_mode: PrefixMode::Optional,
+    prefix_check: PrefixCheck::Excluded,
+    check_position: CheckPosition::Trailing,
+    normalizer: Normalizer::LENIENT,
+    grouping: &[],
+    separator: ' ',
+    rule: None,
+};
+
+/// All the profiles provided.
+pub const PROFILES: [Identifier; 3] = [OIB, STEUER_ID, UST_ID];
+
+/// The structural rule of the German tax identification number, in force since 2016.
+fn steuer_id_rule(digits: &[u8]) -> Result<(), &'static str> {
+    if digits[0] == b'0' {
+        return Err("Steuer-IdNr must not start with 0");
+    }
+
+    let mut counts = [0u8; 10];
+    for &c in &digits[..10] {
+        counts[usize::from(c - b'0')] += 1;
+    }
+    let mut repeated = counts.iter().enumerate().filter(|(_, &n)| n > 1);
+    let (digit, count) = match (repeated.next(), repeated.next()) {
+        (Some((digit, &count)), None) if count <= 3 => (digit as u8 + b'0', count),
+        _ => return Err("Steuer-IdNr must repeat exactly one digit two or three times"),
+    };
+    if count == 3 && digits[..10].windows(3).any(|w| w == [digit; 3]) {
+        return Err("Steuer-IdNr must not repeat a digit three times in a row");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::identifier::IdentifierError;
+    use crate::national_id::*;
+    use crate::{CheckChars, System};
+
+    #[test]
+    fn profiles_use_mod_11_10() {
+        for profile in PROFILES {
+            assert_eq!(
+                MOD_11_10.designation(),
+                profile.system.system_designation(),
+                "{}",
+                profile.name
+            );
+        }
+    }
+
+    #[test]
+    fn validate_oib() {
+        assert_eq!(Ok(()), OIB.validate("69435151530"));
+        assert_eq!(Ok(()), OIB.validate("HR94577403194"));
+        assert_eq!("0", OIB.check_chars("6943515153").unwrap().as_str());
+        assert_eq!(
+            Err(IdentifierError::Check(Error::CheckMismatch {
+                expected: CheckChars::from_ascii_bytes(b"0").unwrap(),
+                found: CheckChars::from_ascii_bytes(b"1").unwrap(),
+            })),
+            OIB.validate("69435151531")
+        );
+        assert_eq!(
+            Err(IdentifierError::WrongLength {
+                minimum: 10,
+                maximum: 10,
+                length: 9
+            }),
+            OIB.validate("6943515153")
+        );
+    }
+
+    #[test]
+    fn validate_steuer_id() {
+        for example in [
+            "86095742719",
+            "47 036 892 816",
+            "65929970489",
+            "57549285017",
+            "25768131411",
+        ] {
+            assert_eq!(Ok(()), STEUER_ID.validate(example), "{}", example);
+        }
+        assert_eq!("9", STEUER_ID.check_chars("8609574271").unwrap().as_str());
+
+        // Valid check digit, but no repeated digit.
+        assert_eq!(
+            Err(IdentifierError::InvalidStructure(
+                "Steuer-IdNr must repeat exactly one digit two or three times"
+            )),
+            STEUER_ID.validate("12345678903")
+        );
+        assert_eq!(
+            Err(IdentifierError::InvalidStructure(
+                "Steuer-IdNr must not start with 0"
+            )),
+            STEUER_ID.validate("01234567896")
+        );
+        assert_eq!(
+            Err(IdentifierError::InvalidStructure(
+                "Steuer-IdNr must not repeat a digit three times in a row"
+            )),
+            STEUER_ID.validate("11123456780")
+        );
+        assert_eq!(
+            Err(IdentifierError::InvalidStructure(
+                "Steuer-IdNr must not start with 0"
+            )),
+            STEUER_ID.check_chars("0123456789")
+        );
+    }
+
+    #[test]
+    fn validate_ust_id() {
+        assert_eq!(Ok(()), UST_ID.validate("DE136695976"));
+        assert_eq!(Ok(()), UST_ID.validate("de 136 695 976"));
+        assert_eq!(Ok(()), UST_ID.validate("136695976"));
+        assert_eq!(
+            Err(IdentifierError::IllegalCharacter {
+                position: 5,
+                byte: b'X'
+            }),
+            UST_ID.validate("DE136X95976")
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn generate_and_format() {
+        assert_eq!(Ok("69435151530"), OIB.generate("HR6943515153").as_deref());
+        assert_eq!(Ok("69435151530"), OIB.format("hr 69435151530").as_deref());
+        assert_eq!(
+            Ok("86 095 742 719"),
+            STEUER_ID.format("86095742719").as_deref()
+        );
+        assert_eq!(
+            Ok("86095742719"),
+            STEUER_ID.generate("8609574271").as_deref()
+        );
+        assert_eq!(Ok("DE136695976"), UST_ID.format("136695976").as_deref());
+        assert_eq!(Ok("DE136695976"), UST_ID.generate("13669597").as_deref());
+    }
+}
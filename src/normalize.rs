@@ -0,0 +1,279 @@
+//! # iso_iec_7064::normalize
+//!
+//! Normalization of input strings before validation or checksum computation.
+//!
+//! The _Alphabets_ of The Standard only contain digits, upper-case letters and the supplementary
+//! check characters, but identifiers are often written in lower case, or with spaces, hyphens or
+//! dots separating groups of characters (for example "gb82 west 1234 5698 7654 32" or
+//! "0000-0002-1825-0097"). A `Normalizer` describes which of these variations to accept.
+
+use crate::error::Error;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// Policy for normalizing input strings. Start from `Normalizer::NONE` or `Normalizer::LENIENT`
+/// and adjust with the builder methods:
+///
+/// ```
+/// use iso_iec_7064::{Normalizer, System, MOD_11_2};
+///
+/// let normalizer = Normalizer::NONE.ignore_hyphens(true);
+/// assert!(MOD_11_2.validate_normalized("0000-0002-1825-0097", &normalizer));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Normalizer {
+    fold_case: bool,
+    ignore_spaces: bool,
+    ignore_hyphens: bool,
+    ignore_dots: bool,
+    strict: bool,
+}
+
+impl Normalizer {
+    /// No normalization at all: the input must already be in the _Alphabet_.
+    pub const NONE: Normalizer = Normalizer {
+        fold_case: false,
+        ignore_spaces: false,
+        ignore_hyphens: false,
+        ignore_dots: false,
+        strict: false,
+    };
+
+    /// Fold lower-case letters to upper-case, and ignore spaces, hyphens and dots.
+    pub const LENIENT: Normalizer = Normalizer {
+        fold_case: true,
+        ignore_spaces: true,
+        ignore_hyphens: true,
+        ignore_dots: true,
+        strict: false,
+    };
+
+    /// Whether to fold ASCII lower-case letters to upper-case.
+    pub const fn fold_case(self, fold_case: bool) -> Normalizer {
+        Normalizer { fold_case, ..self }
+    }
+
+    /// Whether to ignore ASCII spaces.
+    pub const fn ignore_spaces(self, ignore_spaces: bool) -> Normalizer {
+        Normalizer {
+            ignore_spaces,
+            ..self
+        }
+    }
+
+    /// Whether to ignore hyphens ('-').
+    pub const fn ignore_hyphens(self, ignore_hyphens: bool) -> Normalizer {
+        Normalizer {
+            ignore_hyphens,
+            ..self
+        }
+    }
+
+    /// Whether to ignore dots ('.').
+    pub const fn ignore_dots(self, ignore_dots: bool) -> Normalizer {
+        Normalizer {
+            ignore_dots,
+            ..self
+        }
+    }
+
+    /// In strict mode, ignorable characters are not silently stripped: the first one encountered
+    /// is reported as `Error::StrippedCharacter` (by `check_strict`, and by the `try_` methods of
+    /// `System`), and the `bool` and `Option` methods of `System` fail. Case folding still applies.
+    pub const fn strict(self, strict: bool) -> Normalizer {
+        Normalizer { strict, ..self }
+    }
+
+    /// Returns true if this is a strict normalizer.
+    pub const fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns true if the ASCII byte is stripped by this normalizer.
+    pub const fn is_ignored(&self, c: u8) -> bool {
+        match c {
+            b' ' => self.ignore_spaces,
+            b'-' => self.ignore_hyphens,
+            b'.' => self.ignore_dots,
+            _ => false,
+        }
+    }
+
+    /// Map an ASCII byte to its normalized form (which is only different when folding case).
+    pub const fn map_byte(&self, c: u8) -> u8 {
+        if self.fold_case {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        }
+    }
+
+    /// In strict mode, report the first character that would be stripped, with its (zero-based)
+    /// position in the input. Always succeeds in non-strict mode.
+    pub fn check_strict(&self, bytes: &[u8]) -> Result<(), Error> {
+        if !self.strict {
+            return Ok(());
+        }
+        match bytes.iter().position(|&c| self.is_ignored(c)) {
+            Some(position) => Err(Error::StrippedCharacter {
+                position,
+                byte: bytes[position],
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Normalize the input bytes on the fly, without allocating. Ignorable characters are
+    /// skipped regardless of strict mode; use `check_strict` to detect them.
+    pub fn normalize_iter<'a>(&'a self, bytes: &'a [u8]) -> impl Iterator<Item = u8> + 'a {
+        bytes
+            .iter()
+            .filter(move |&&c| !self.is_ignored(c))
+            .map(move |&c| self.map_byte(c))
+    }
+
+    /// Normalize the input bytes into the buffer, without allocating, returning the normalized
+    /// length. If that exceeds the length of the buffer, the excess is dropped.
+    pub(crate) fn normalize_into(&self, bytes: &[u8], buffer: &mut [u8]) -> usize {
+        let mut length = 0;
+        for c in self.normalize_iter(bytes) {
+            if length < buffer.len() {
+                buffer[length] = c;
+            }
+            length += 1;
+        }
+        length
+    }
+
+    /// Normalize the input string, remembering which characters were stripped and where each
+    /// remaining character came from. Ignorable characters are stripped regardless of strict mode.
+    #[cfg(feature = "alloc")]
+    pub fn normalize(&self, string: &str) -> Normalized {
+        let mut bytes = Vec::with_capacity(string.len());
+        let mut positions = Vec::with_capacity(string.len());
+        let mut stripped = Vec::new();
+
+        for (position, &c) in string.as_bytes().iter().enumerate() {
+            if self.is_ignored(c) {
+                stripped.push((position, c));
+            } else {
+                bytes.push(self.map_byte(c));
+                positions.push(position);
+            }
+        }
+
+        Normalized {
+            // Only ASCII bytes were removed or changed, so the result is still valid UTF-8.
+            value: String::from_utf8(bytes).expect("normalization preserves UTF-8"),
+            positions,
+            stripped,
+        }
+    }
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Normalizer::NONE
+    }
+}
+
+/// Remove the prefix from the start of the input if it is there, ignoring ASCII case.
+pub(crate) fn strip_prefix_ignore_case<'a>(input: &'a str, prefix: &str) -> Option<&'a str> {
+    match input.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => Some(&input[prefix.len()..]),
+        _ => None,
+    }
+}
+
+/// The result of `Normalizer::normalize`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Normalized {
+    value: String,
+    positions: Vec<usize>,
+    stripped: Vec<(usize, u8)>,
+}
+
+#[cfg(feature = "alloc")]
+impl Normalized {
+    /// The normalized string.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// The characters that were stripped, with their (zero-based) positions in the input.
+    pub fn stripped(&self) -> &[(usize, u8)] {
+        &self.stripped
+    }
+
+    /// The position in the input of the character at the given position in the normalized string.
+    pub fn original_position(&self, position: usize) -> usize {
+        self.positions.get(position).copied().unwrap_or(position)
+    }
+
+    /// Translate the positions reported by an error about the normalized string into positions in
+    /// the input.
+    pub fn original_error(&self, error: Error) -> Error {
+        match error {
+            Error::IllegalCharacter { position, byte } => Error::IllegalCharacter {
+                position: self.original_position(position),
+                byte,
+            },
+            Error::SupplementaryCharacterInPayload { position, byte } => {
+                Error::SupplementaryCharacterInPayload {
+                    position: self.original_position(position),
+                    byte,
+                }
+            }
+            error => error,
+        }
+    }
+
+    /// Consume, returning the normalized string.
+    pub fn into_string(self) -> String {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::normalize::Normalizer;
+
+    #[test]
+    fn lenient_folds_case_and_strips_separators() {
+        let normalizer = Normalizer::LENIENT;
+        assert!(normalizer
+            .normalize_iter(b"gb-8 2.w")
+            .eq(b"GB82W".iter().copied()));
+        assert_eq!(Ok(()), normalizer.check_strict(b"gb-8 2.w"));
+    }
+
+    #[test]
+    fn none_changes_nothing() {
+        assert!(Normalizer::NONE
+            .normalize_iter(b"a -.")
+            .eq(b"a -.".iter().copied()));
+    }
+
+    #[test]
+    fn strict_reports_first_stripped_char() {
+        let normalizer = Normalizer::LENIENT.strict(true);
+        assert_eq!(
+            Err(crate::Error::StrippedCharacter {
+                position: 4,
+                byte: b' '
+            }),
+            normalizer.check_strict(b"GB82 WEST")
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn normalize_records_stripped_chars_and_positions() {
+        let normalized = Normalizer::LENIENT.normalize("0000-0002 x");
+        assert_eq!("00000002X", normalized.as_str());
+        assert_eq!(&[(4, b'-'), (9, b' ')], normalized.stripped());
+        assert_eq!(10, normalized.original_position(8));
+    }
+}
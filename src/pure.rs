@@ -2,12 +2,15 @@
 //!
 //! A trait to help implement the _Pure Check Character Systems_ appearing in The Standard.
 
-use crate::system::System;
+use crate::alphabet::Alphabet;
+use crate::error::Error;
+use crate::system::{CheckChars, System};
 
 /// This is the state that will change with each iteration. Constant generic parameter CHECK_LENGTH
 /// must be non-zero. The type of CHECK_LENGTH is usize instead of u8 because even though it in
 /// practice only ever contains value 1 or 2, it is used in the size of an array, requiring it to be
 /// of type usize.
+#[derive(Clone, Debug)]
 struct State<
     const CHECK_LENGTH: usize,
     const MAX_DIGIT_VALUE: u8,
@@ -36,13 +39,15 @@ impl<
     /// isn't one. These are used during validation to detect illegal characters in the payload
     /// portion
     /// of the input string.
-    const SUPPLEMENTARY_CHAR_VALUE: i8 = match MAX_DIGIT_VALUE {
-        10 => 10,
-        36 => 36,
-        _ => -1,
+    const SUPPLEMENTARY_CHAR_VALUE: i8 = match Alphabet::from_max_digit_value(MAX_DIGIT_VALUE) {
+        Some(alphabet) => match alphabet.supplementary_value() {
+            Some(v) => v as i8,
+            None => -1,
+        },
+        None => -1,
     };
 
-    fn new() -> State<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX> {
+    const fn new() -> State<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX> {
         State {
             count: 0,
             sum: 0,
@@ -52,7 +57,7 @@ impl<
 
     /// Returns true if it successfully processed the digit value, false otherwise (for example, if
     /// the value was out of range).
-    fn process_digit_value(&mut self, v: u8) -> bool {
+    const fn process_digit_value(&mut self, v: u8) -> bool {
         if v > MAX_DIGIT_VALUE {
             return false;
         }
@@ -68,8 +73,10 @@ impl<
                 if self.check_char_values[0] == (n as u8) {
                     return false;
                 }
-                for i in 1..CHECK_LENGTH {
-                    self.check_char_values[i - 1] = self.check_char_values[i]
+                let mut i = 1;
+                while i < CHECK_LENGTH {
+                    self.check_char_values[i - 1] = self.check_char_values[i];
+                    i += 1;
                 }
                 self.check_char_values[CHECK_LENGTH - 1] = v;
             }
@@ -85,9 +92,102 @@ impl<
 
         true
     }
+
+    /// Returns true if the digit values processed so far, which must include the check digit(s),
+    /// satisfy the check.
+    const fn is_valid(&self) -> bool {
+        // If we have processed fewer than `check_length` + 1 items, then the input cannot be valid
+        // because it has no Payload.
+        if self.count < (CHECK_LENGTH + 1) {
+            return false;
+        }
+
+        self.sum % MODULUS == 1
+    }
+
+    /// Returns the checksum for the payload digit values processed so far, or None if there is
+    /// no Payload or it ends with a _Supplementary Check Character_.
+    const fn checksum(mut self) -> Option<u16> {
+        // Act as if we had zero(s) provided for the check digit position(s).
+        let mut i = 0;
+        while i < CHECK_LENGTH {
+            if !self.process_digit_value(0) {
+                return None;
+            }
+            i += 1;
+        }
+
+        // If we have processed no items, then the input cannot be valid because it has no Payload.
+        if self.count < (CHECK_LENGTH + 1) {
+            return None;
+        }
+
+        let value = ((MODULUS + 1) - (self.sum % MODULUS)) % MODULUS;
+
+        Some(value as u16)
+    }
+}
+
+/// Incremental computation for a _Pure Check Character System_, for when the input arrives in
+/// several pieces. Obtain one from `PureSystem::hasher`, feed it ASCII bytes with `update`, and
+/// call `finalize_checksum` or `finalize_validate` as many times as needed. Cloning a hasher
+/// captures the intermediate state so different continuations can be explored.
+#[derive(Clone, Debug)]
+pub struct PureHasher<
+    const CHECK_LENGTH: usize,
+    const MAX_DIGIT_VALUE: u8,
+    const MODULUS: usize,
+    const RADIX: usize,
+> {
+    alphabet: Alphabet,
+    state: State<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX>,
+    /// Set to false once a byte outside the ALPHABET (or misplaced _Supplementary Check
+    /// Character_) has been encountered, after which no input can be valid.
+    ok: bool,
+}
+
+impl<
+        const CHECK_LENGTH: usize,
+        const MAX_DIGIT_VALUE: u8,
+        const MODULUS: usize,
+        const RADIX: usize,
+    > PureHasher<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX>
+{
+    /// Feed more ASCII bytes of the input. If a byte outside the ALPHABET is encountered, the
+    /// remaining input is ignored and both finalize methods will report failure.
+    pub fn update(&mut self, bytes: &[u8]) {
+        if !self.ok {
+            return;
+        }
+        for &c in bytes {
+            match self.alphabet.char_value(c) {
+                Some(v) if self.state.process_digit_value(v) => (),
+                _ => {
+                    self.ok = false;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// The checksum for the bytes fed so far, treated as the payload. Returns None if there is no
+    /// payload or it contains characters outside the ALPHABET.
+    pub fn finalize_checksum(&self) -> Option<u16> {
+        if !self.ok {
+            return None;
+        }
+        self.state.clone().checksum()
+    }
+
+    /// Returns true if the bytes fed so far, treated as payload with the check character(s)
+    /// appended, satisfy the check.
+    pub fn finalize_validate(&self) -> bool {
+        self.ok && self.state.is_valid()
+    }
 }
 
 /// Type for implementing all _Pure Check Character Systems_
+#[derive(Clone, Copy, Debug)]
 pub struct PureSystem<
     const CHECK_LENGTH: usize,
     const MAX_DIGIT_VALUE: u8,
@@ -96,6 +196,7 @@ pub struct PureSystem<
 > {
     pub(crate) name: &'static str,
     pub(crate) designation: u8,
+    pub(crate) alphabet: Alphabet,
 }
 
 impl<
@@ -105,6 +206,55 @@ impl<
         const RADIX: usize,
     > PureSystem<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX>
 {
+    /// Validate that the input ASCII bytes, which must already have the check digit(s) appended,
+    /// satisfy the check. This is a `const fn`, so it can be used to check identifiers at compile
+    /// time (see `const_assert_valid!`).
+    pub const fn validate_ascii_bytes(&self, bytes: &[u8]) -> bool {
+        let mut state: State<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX> = State::new();
+
+        let mut i = 0;
+        while i < bytes.len() {
+            let v = match self.alphabet.char_value(bytes[i]) {
+                Some(v) => v,
+                None => return false,
+            };
+            if !state.process_digit_value(v) {
+                return false;
+            }
+            i += 1;
+        }
+
+        state.is_valid()
+    }
+
+    /// Compute the checksum for payload ASCII bytes. This is a `const fn`, so it can be used to
+    /// compute check digits at compile time.
+    pub const fn checksum_ascii_bytes(&self, bytes: &[u8]) -> Option<u16> {
+        let mut state: State<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX> = State::new();
+
+        let mut i = 0;
+        while i < bytes.len() {
+            let v = match self.alphabet.char_value(bytes[i]) {
+                Some(v) => v,
+                None => return None,
+            };
+            if !state.process_digit_value(v) {
+                return None;
+            }
+            i += 1;
+        }
+
+        state.checksum()
+    }
+
+    /// Start an incremental computation with this system.
+    pub fn hasher(&self) -> PureHasher<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX> {
+        PureHasher {
+            alphabet: self.alphabet,
+            state: State::new(),
+            ok: true,
+        }
+    }
 }
 
 impl<
@@ -122,9 +272,9 @@ impl<
         self.designation
     }
 
-    // fn alphabet(&self) -> Alphabet<MAX_DIGIT_VALUE> {
-    //     Self::ALPHABET
-    // }
+    fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
 
     fn check_length(&self) -> u8 {
         CHECK_LENGTH as u8
@@ -145,13 +295,7 @@ impl<
             }
         }
 
-        // If we have processed fewer than `check_length` + 1 items, then the input cannot be valid
-        // because it has no Payload.
-        if state.count < (CHECK_LENGTH + 1) {
-            return false;
-        }
-
-        state.sum % MODULUS == 1
+        state.is_valid()
     }
 
     /// Compute the checksum for an iterator of payload digit values (for example, values in the
@@ -169,22 +313,70 @@ impl<
             }
         }
 
-        // Act as if we had zero(s) provided for the check digit position(s).
-        for _ in 0..CHECK_LENGTH {
-            if !state.process_digit_value(0) {
-                return None;
+        state.checksum()
+    }
+
+    fn try_validate_ascii_bytes(&self, bytes: &[u8]) -> Result<(), Error> {
+        // There must be at least one Payload character before the check character(s).
+        if bytes.len() < (CHECK_LENGTH + 1) {
+            return Err(Error::TooShort {
+                minimum: CHECK_LENGTH + 1,
+                length: bytes.len(),
+            });
+        }
+
+        let payload_length = bytes.len() - CHECK_LENGTH;
+        let mut state: State<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX> = State::new();
+
+        for (position, &byte) in bytes.iter().enumerate() {
+            let v = self
+                .alphabet
+                .char_value(byte)
+                .ok_or(Error::IllegalCharacter { position, byte })?;
+            if position < payload_length && v >= self.alphabet.radix() {
+                return Err(Error::SupplementaryCharacterInPayload { position, byte });
             }
+            state.process_digit_value(v);
         }
 
-        // If we have processed no items, then the input cannot be valid because it has no Payload.
-        // NOTE: We have to check this after all the calls to process_digit_value() so we can get
-        // back ownership of the state.
-        if state.count < (CHECK_LENGTH + 1) {
-            return None;
+        if state.is_valid() {
+            return Ok(());
         }
 
-        let value = ((MODULUS + 1) - (state.sum % MODULUS)) % MODULUS;
+        let expected = self.try_checksum_ascii_bytes(&bytes[..payload_length])?;
+        Err(Error::CheckMismatch {
+            expected: self
+                .check_chars(expected)
+                .expect("checksum is always representable"),
+            found: CheckChars::from_ascii_bytes(&bytes[payload_length..])
+                .expect("check characters are in the alphabet"),
+        })
+    }
+
+    fn try_checksum_ascii_bytes(&self, bytes: &[u8]) -> Result<u16, Error> {
+        // There must be at least one Payload character.
+        if bytes.is_empty() {
+            return Err(Error::TooShort {
+                minimum: 1,
+                length: 0,
+            });
+        }
 
-        Some(value as u16)
+        let mut state: State<CHECK_LENGTH, MAX_DIGIT_VALUE, MODULUS, RADIX> = State::new();
+
+        for (position, &byte) in bytes.iter().enumerate() {
+            let v = self
+                .alphabet
+                .char_value(byte)
+                .ok_or(Error::IllegalCharacter { position, byte })?;
+            if v >= self.alphabet.radix() {
+                return Err(Error::SupplementaryCharacterInPayload { position, byte });
+            }
+            state.process_digit_value(v);
+        }
+
+        Ok(state
+            .checksum()
+            .expect("payload is non-empty and free of supplementary characters"))
     }
 }
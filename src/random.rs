@@ -0,0 +1,335 @@
+//! # iso_iec_7064::random
+//!
+//! Random valid strings, and near-miss invalid variants of them, for load tests and fixtures.
+//!
+//! The crate has no dependency on a random number generator: callers supply one through the `Rng`
+//! trait, which is implemented for any `FnMut() -> u64` closure, so adapting a generator from
+//! another crate is a one-liner:
+//!
+//! ```
+//! use iso_iec_7064::analysis::ErrorClass;
+//! use iso_iec_7064::random::{near_miss, valid};
+//! use iso_iec_7064::{System, MOD_97_10};
+//!
+//! // A tiny linear congruential generator, standing in for a real one.
+//! let mut state = 7064u64;
+//! let mut rng = || {
+//!     state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+//!     state
+//! };
+//!
+//! let id = valid(&MOD_97_10, 8, &mut rng).unwrap();
+//! assert_eq!(10, id.len());
+//! assert!(MOD_97_10.validate_string(&id));
+//!
+//! let typo = near_miss(&MOD_97_10, &id, ErrorClass::AdjacentTransposition, &mut rng).unwrap();
+//! assert!(!MOD_97_10.validate_string(&typo));
+//! ```
+
+use core::fmt;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::analysis::{for_each_variant, ErrorClass, Symbols};
+use crate::error::Error;
+use crate::system::System;
+
+/// A source of random numbers.
+pub trait Rng {
+    /// The next 64 random bits.
+    fn next_u64(&mut self) -> u64;
+
+    /// A random value below `bound`, which must not be zero. The bias is negligible for bounds
+    /// much smaller than 2<sup>32</sup>, such as the sizes of the _Alphabets_.
+    fn below(&mut self, bound: usize) -> usize {
+        (((self.next_u64() >> 32) * bound as u64) >> 32) as usize
+    }
+}
+
+impl<F> Rng for F
+where
+    F: FnMut() -> u64,
+{
+    fn next_u64(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// Placeholder in a pattern for any payload symbol of the _Alphabet_.
+pub const ANY: u8 = b'?';
+/// Placeholder in a pattern for a digit.
+pub const DIGIT: u8 = b'#';
+/// Placeholder in a pattern for a letter.
+pub const LETTER: u8 = b'@';
+
+/// The reason a random string could not be generated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GenerateError {
+    /// A placeholder in the pattern, at the given (zero-based) position, matches no payload symbol
+    /// of the _Alphabet_ (for example, `LETTER` with a numeric system).
+    UnsatisfiablePlaceholder { position: usize, placeholder: u8 },
+    /// The payload could not be completed with check character(s), for example because it is
+    /// empty or the pattern has a literal character outside the _Alphabet_.
+    Check(Error),
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::UnsatisfiablePlaceholder {
+                position,
+                placeholder,
+            } => write!(
+                f,
+                "placeholder {:?} at position {} matches no symbol of the alphabet",
+                *placeholder as char, position
+            ),
+            GenerateError::Check(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GenerateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GenerateError::Check(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for GenerateError {
+    fn from(error: Error) -> Self {
+        GenerateError::Check(error)
+    }
+}
+
+/// Append the check character(s) for the payload.
+fn complete<const MAX_DIGIT_VALUE: u8, S>(
+    system: &S,
+    mut payload: Vec<u8>,
+) -> Result<String, GenerateError>
+where
+    S: System<MAX_DIGIT_VALUE> + ?Sized,
+{
+    let checksum = system.try_checksum_ascii_bytes(&payload)?;
+    let check = system
+        .check_chars(checksum)
+        .expect("checksum is always representable");
+    payload.extend_from_slice(check.as_bytes());
+    // The payload was accepted by the system, so it only has ALPHABET symbols.
+    Ok(String::from_utf8(payload).expect("valid strings are ASCII"))
+}
+
+/// Generate a random payload of the given length, completed with its check character(s).
+pub fn valid<const MAX_DIGIT_VALUE: u8, S, R>(
+    system: &S,
+    payload_length: usize,
+    rng: &mut R,
+) -> Result<String, GenerateError>
+where
+    S: System<MAX_DIGIT_VALUE> + ?Sized,
+    R: Rng + ?Sized,
+{
+    let symbols = system.alphabet().payload_symbols().as_bytes();
+    let payload = (0..payload_length)
+        .map(|_| symbols[rng.below(symbols.len())])
+        .collect();
+    complete(system, payload)
+}
+
+/// Generate a random payload matching the pattern, completed with its check character(s). In the
+/// pattern, `ANY` ('?') stands for any payload symbol, `DIGIT` ('#') for a digit and `LETTER`
+/// ('@') for a letter; all other characters are copied as they are.
+pub fn valid_matching<const MAX_DIGIT_VALUE: u8, S, R>(
+    system: &S,
+    pattern: &str,
+    rng: &mut R,
+) -> Result<String, GenerateError>
+where
+    S: System<MAX_DIGIT_VALUE> + ?Sized,
+    R: Rng + ?Sized,
+{
+    let symbols = system.alphabet().payload_symbols().as_bytes();
+    let digits = symbols.iter().position(|c| !c.is_ascii_digit());
+    // Payload symbols are digits followed by letters, so each class is a contiguous range.
+    let (digits, letters) = symbols.split_at(digits.unwrap_or(symbols.len()));
+
+    let mut payload = Vec::with_capacity(pattern.len());
+    for (position, c) in pattern.bytes().enumerate() {
+        let choices = match c {
+            ANY => symbols,
+            DIGIT => digits,
+            LETTER => letters,
+            _ => {
+                payload.push(c);
+                continue;
+            }
+        };
+        if choices.is_empty() {
+            return Err(GenerateError::UnsatisfiablePlaceholder {
+                position,
+                placeholder: c,
+            });
+        }
+        payload.push(choices[rng.below(choices.len())]);
+    }
+    complete(system, payload)
+}
+
+/// Apply one random error of the class to a valid string, returning a variant that fails the
+/// check, or `None` if no error of the class applies (for example, twin errors in a string without
+/// doubled characters) or none of them is detected. Panics if `valid` is not ASCII, as no string
+/// that satisfies the check can contain other characters.
+pub fn near_miss<const MAX_DIGIT_VALUE: u8, S, R>(
+    system: &S,
+    valid: &str,
+    class: ErrorClass,
+    rng: &mut R,
+) -> Option<String>
+where
+    S: System<MAX_DIGIT_VALUE> + ?Sized,
+    R: Rng + ?Sized,
+{
+    assert!(valid.is_ascii(), "near_miss needs an ASCII string");
+    let mut string = Vec::from(valid.as_bytes());
+    let symbols = Symbols::new(system, string.len());
+
+    // Choose uniformly among the detected variants, by reservoir sampling.
+    let mut chosen = Vec::new();
+    let mut seen = 0;
+    for_each_variant(class, &mut string, &symbols, |variant| {
+        if system.validate_ascii_bytes_iter(variant.iter().copied()) {
+            return;
+        }
+        seen += 1;
+        if rng.below(seen) == 0 {
+            chosen.clear();
+            chosen.extend_from_slice(variant);
+        }
+    });
+
+    if seen == 0 {
+        None
+    } else {
+        // Errors only introduce ALPHABET symbols, so an ASCII input stays ASCII.
+        Some(String::from_utf8(chosen).expect("variants of ASCII strings are ASCII"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::SplitMix64;
+    use crate::random::*;
+    use crate::{MOD_11_2, MOD_27_26, MOD_37_36, MOD_97_10};
+
+    #[test]
+    fn generate_valid_strings_of_a_length() {
+        let mut rng = SplitMix64(1);
+        for _ in 0..100 {
+            let id = valid(&MOD_11_2, 15, &mut rng).unwrap();
+            assert_eq!(16, id.len());
+            assert!(MOD_11_2.validate_string(&id));
+            assert!(id[..15].bytes().all(|c| c.is_ascii_digit()));
+        }
+        assert_eq!(
+            Err(GenerateError::Check(Error::TooShort {
+                minimum: 1,
+                length: 0
+            })),
+            valid(&MOD_11_2, 0, &mut rng)
+        );
+    }
+
+    #[test]
+    fn generate_valid_strings_matching_a_pattern() {
+        let mut rng = SplitMix64(2);
+        for _ in 0..100 {
+            let id = valid_matching(&MOD_37_36, "A1-@@###-?", &mut rng);
+            // The hyphens are literal, and not in the alphabet.
+            assert_eq!(
+                Err(GenerateError::Check(Error::IllegalCharacter {
+                    position: 2,
+                    byte: b'-'
+                })),
+                id
+            );
+
+            let id = valid_matching(&MOD_37_36, "A1@@###?", &mut rng).unwrap();
+            assert!(MOD_37_36.validate_string(&id));
+            assert!(id.starts_with("A1"));
+            assert!(id[2..4].bytes().all(|c| c.is_ascii_uppercase()));
+            assert!(id[4..7].bytes().all(|c| c.is_ascii_digit()));
+        }
+        assert_eq!(
+            Err(GenerateError::UnsatisfiablePlaceholder {
+                position: 1,
+                placeholder: b'@'
+            }),
+            valid_matching(&MOD_97_10, "1@", &mut rng)
+        );
+        assert_eq!(
+            Err(GenerateError::UnsatisfiablePlaceholder {
+                position: 0,
+                placeholder: b'#'
+            }),
+            valid_matching(&MOD_27_26, "#", &mut rng)
+        );
+    }
+
+    #[test]
+    fn near_misses_fail_the_check() {
+        let mut rng = SplitMix64(3);
+        for class in [
+            ErrorClass::SingleSubstitution,
+            ErrorClass::AdjacentTransposition,
+            ErrorClass::JumpTransposition,
+            ErrorClass::Twin,
+        ] {
+            for _ in 0..50 {
+                let id = valid_matching(&MOD_97_10, "11??????", &mut rng).unwrap();
+                let typo = near_miss(&MOD_97_10, &id, class, &mut rng).unwrap();
+                assert!(!MOD_97_10.validate_string(&typo), "{} {}", class, typo);
+                assert_eq!(id.len(), typo.len());
+                let differences = id.bytes().zip(typo.bytes()).filter(|(a, b)| a != b);
+                let expected = match class {
+                    ErrorClass::SingleSubstitution => 1,
+                    _ => 2,
+                };
+                assert_eq!(expected, differences.count(), "{} {} {}", class, id, typo);
+            }
+        }
+    }
+
+    #[test]
+    fn near_miss_needs_an_applicable_error() {
+        let mut rng = SplitMix64(4);
+        assert_eq!(
+            None,
+            near_miss(&MOD_97_10, "123456", ErrorClass::Twin, &mut rng)
+        );
+        assert_eq!(
+            Some(String::from("70940")),
+            near_miss(
+                &MOD_11_2,
+                "07940",
+                ErrorClass::AdjacentTransposition,
+                // Always keeps the first variant.
+                &mut || u64::MAX
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "near_miss needs an ASCII string")]
+    fn near_miss_rejects_non_ascii_input() {
+        near_miss(
+            &MOD_97_10,
+            "79444\u{e9}",
+            ErrorClass::SingleSubstitution,
+            &mut SplitMix64(5),
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! # iso_iec_7064::rf_reference
+//!
+//! Structured Creditor References, as specified by ISO 11649:2009, built on `MOD_97_10`.
+//!
+//! An RF creditor reference consists of the letters "RF", two check digits and a creditor
+//! reference of 1 to 21 letters and digits chosen by the creditor. As for IBAN, the check digits
+//! are computed with MOD 97-10 over the creditor reference followed by "RF", with each letter
+//! expanded to two decimal digits (A = 10, ..., Z = 35), and are "98 minus" the remainder, so they
+//! are always in the range 02 to 98.
+//!
+//! The functions here accept both the electronic format ("RF18539007547034") and the print format
+//! ("RF18 5390 0754 7034"), in either case. Positions reported in errors refer to the electronic
+//! format.
+
+use core::fmt;
+
+use crate::check_digits;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// The prefix of every RF creditor reference.
+pub const PREFIX: &str = "RF";
+
+/// The maximum length of the creditor reference, excluding the prefix and check digits.
+pub const MAX_REFERENCE_LENGTH: usize = 21;
+
+/// The maximum length of an RF creditor reference in electronic format.
+pub const MAX_LENGTH: usize = PREFIX.len() + 2 + MAX_REFERENCE_LENGTH;
+
+/// The name of RF creditor references in error messages.
+const NAME: &str = "RF creditor reference";
+
+/// The reason an RF creditor reference was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RfError {
+    /// The input does not start with "RF".
+    MissingPrefix,
+    /// The creditor reference is empty.
+    EmptyReference,
+    /// The input is longer than allowed.
+    TooLong { maximum: usize, length: usize },
+    /// The check digits are not two digits in the range 02 to 98.
+    InvalidCheckDigits,
+    /// A character other than a letter or digit was encountered at the given (zero-based)
+    /// position.
+    IllegalCharacter { position: usize, byte: u8 },
+    /// The reference is well-formed, but the check digits do not satisfy the check.
+    CheckMismatch { expected: u8, found: u8 },
+}
+
+impl fmt::Display for RfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RfError::MissingPrefix => f.write_str("RF creditor reference does not start with RF"),
+            RfError::EmptyReference => f.write_str("RF creditor reference is empty"),
+            RfError::TooLong { maximum, length } => write!(
+                f,
+                "RF creditor reference length {} exceeds {}",
+                length, maximum
+            ),
+            RfError::InvalidCheckDigits => check_digits::write_invalid(f, NAME),
+            RfError::IllegalCharacter { position, byte } => {
+                check_digits::write_illegal_character(f, NAME, *position, *byte)
+            }
+            RfError::CheckMismatch { expected, found } => {
+                check_digits::write_mismatch(f, NAME, *expected, *found)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RfError {}
+
+/// Normalize the input into the buffer, failing if it is too long for the buffer.
+fn electronic<'a>(input: &str, buffer: &'a mut [u8]) -> Result<&'a [u8], RfError> {
+    let maximum = buffer.len();
+    check_digits::electronic(input, buffer).map_err(|length| RfError::TooLong { maximum, length })
+}
+
+/// Check that the creditor reference is non-empty and alphanumeric, reporting positions offset by
+/// `start`.
+fn check_reference(reference: &[u8], start: usize) -> Result<(), RfError> {
+    if reference.is_empty() {
+        return Err(RfError::EmptyReference);
+    }
+    match reference
+        .iter()
+        .position(|c| !(c.is_ascii_digit() || c.is_ascii_uppercase()))
+    {
+        Some(i) => Err(RfError::IllegalCharacter {
+            position: start + i,
+            byte: reference[i],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Validate an RF creditor reference, reporting the reason if it is not valid.
+pub fn validate(rf: &str) -> Result<(), RfError> {
+    let mut buffer = [0u8; MAX_LENGTH];
+    let rf = electronic(rf, &mut buffer)?;
+
+    if !rf.starts_with(PREFIX.as_bytes()) {
+        return Err(RfError::MissingPrefix);
+    }
+    if rf.len() < PREFIX.len() + 2 {
+        return Err(RfError::InvalidCheckDigits);
+    }
+    let (prefix, rest) = rf.split_at(PREFIX.len());
+    let (check, reference) = rest.split_at(2);
+    let found = check_digits::parse(check).ok_or(RfError::InvalidCheckDigits)?;
+    check_reference(reference, PREFIX.len() + 2)?;
+
+    let expected = check_digits::compute(prefix, reference);
+    if expected == found {
+        Ok(())
+    } else {
+        Err(RfError::CheckMismatch { expected, found })
+    }
+}
+
+/// Returns true if the RF creditor reference is valid.
+pub fn is_valid(rf: &str) -> bool {
+    validate(rf).is_ok()
+}
+
+/// Compute the check digits (02 to 98) for a creditor reference of 1 to 21 letters and digits.
+/// Spaces are ignored and lower-case letters are accepted.
+pub fn check_digits(reference: &str) -> Result<u8, RfError> {
+    let mut buffer = [0u8; MAX_REFERENCE_LENGTH];
+    let reference = electronic(reference, &mut buffer)?;
+    check_reference(reference, 0)?;
+    Ok(check_digits::compute(PREFIX.as_bytes(), reference))
+}
+
+/// Generate a complete RF creditor reference, in electronic format, from a creditor reference of
+/// 1 to 21 letters and digits.
+#[cfg(feature = "alloc")]
+pub fn generate(reference: &str) -> Result<String, RfError> {
+    let check = check_digits(reference)?;
+    let mut rf = String::with_capacity(MAX_LENGTH);
+    rf.push_str(PREFIX);
+    rf.extend(check_digits::to_ascii(check).map(char::from));
+    rf.push_str(&check_digits::to_electronic(reference));
+    Ok(rf)
+}
+
+/// Convert a valid RF creditor reference to electronic format: upper-case, without spaces.
+#[cfg(feature = "alloc")]
+pub fn to_electronic(rf: &str) -> Result<String, RfError> {
+    validate(rf)?;
+    Ok(check_digits::to_electronic(rf))
+}
+
+/// Convert a valid RF creditor reference to print format: upper-case, in groups of four
+/// characters separated by spaces.
+#[cfg(feature = "alloc")]
+pub fn to_print(rf: &str) -> Result<String, RfError> {
+    validate(rf)?;
+    Ok(check_digits::to_print(rf))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rf_reference::*;
+
+    #[test]
+    fn validate_examples() {
+        assert_eq!(Ok(()), validate("RF18539007547034"));
+        assert_eq!(Ok(()), validate("RF18 5390 0754 7034"));
+        assert_eq!(Ok(()), validate("rf18 0000 0000 0539 0075 4703 4"));
+        assert_eq!(Ok(()), validate("RF25A"));
+        assert_eq!(Ok(()), validate("RF95ABCDEFGHIJKLMNOPQRSTU"));
+        assert_eq!(Ok(()), validate("RF040"));
+    }
+
+    #[test]
+    fn validate_reports_reasons() {
+        assert_eq!(
+            Err(RfError::CheckMismatch {
+                expected: 18,
+                found: 19
+            }),
+            validate("RF19539007547034")
+        );
+        assert_eq!(Err(RfError::MissingPrefix), validate("RS18539007547034"));
+        assert_eq!(
+            Err(RfError::InvalidCheckDigits),
+            validate("RF01539007547034")
+        );
+        assert_eq!(Err(RfError::InvalidCheckDigits), validate("RF1"));
+        assert_eq!(Err(RfError::EmptyReference), validate("RF18"));
+        assert_eq!(
+            Err(RfError::IllegalCharacter {
+                position: 8,
+                byte: b'-'
+            }),
+            validate("RF185390-07547034")
+        );
+        assert_eq!(
+            Err(RfError::TooLong {
+                maximum: 25,
+                length: 26
+            }),
+            validate("RF95ABCDEFGHIJKLMNOPQRSTUV")
+        );
+    }
+
+    #[test]
+    fn check_digits_examples() {
+        assert_eq!(Ok(18), check_digits("539007547034"));
+        assert_eq!(Ok(18), check_digits("5390 0754 7034"));
+        assert_eq!(Ok(25), check_digits("a"));
+        assert_eq!(Err(RfError::EmptyReference), check_digits(""));
+        assert_eq!(
+            Err(RfError::TooLong {
+                maximum: 21,
+                length: 22
+            }),
+            check_digits("ABCDEFGHIJKLMNOPQRSTUV")
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn generate_and_format() {
+        assert_eq!(Ok("RF18539007547034"), generate("539007547034").as_deref());
+        assert_eq!(Ok("RF040"), generate("0").as_deref());
+        assert_eq!(
+            Ok("RF18 5390 0754 7034"),
+            to_print("rf18539007547034").as_deref()
+        );
+        assert_eq!(
+            Ok("RF18539007547034"),
+            to_electronic("RF18 5390 0754 7034").as_deref()
+        );
+    }
+}
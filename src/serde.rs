@@ -0,0 +1,383 @@
+//! # iso_iec_7064::serde
+//!
+//! Integration with [serde](https://serde.rs), enabled by the `serde` feature.
+//!
+//! `Checked<S>` serializes as a string, and deserializing one validates it, so invalid check
+//! characters are rejected at the deserialization boundary. For plain `String` fields, the modules
+//! here named after each system validate the same way:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Payment {
+//!     #[serde(with = "iso_iec_7064::serde::mod_97_10")]
+//!     reference: String,
+//! }
+//!
+//! let payment: Payment = serde_json::from_str(r#"{"reference": "79444"}"#).unwrap();
+//! assert_eq!("79444", payment.reference);
+//! assert!(serde_json::from_str::<Payment>(r#"{"reference": "79445"}"#).is_err());
+//! ```
+//!
+//! The identifier types `Isni`, `Grid`, `Isan`, `Lei` and `ResidentId` serialize as strings in
+//! their canonical forms, and deserialize from any of the forms their `FromStr` implementations
+//! accept, so they are validated at the deserialization boundary too.
+//!
+//! `StandardSystem` serializes as its name (such as "ISO/IEC 7064, MOD 97-10"), and deserializes
+//! from a string: a name, in any of the forms accepted by `system_by_name`, or a designation
+//! number such as "3". Use the `designation` module to serialize it as its designation number
+//! instead.
+//!
+//! All these implementations ask the deserializer for the type they expect, rather than relying
+//! on a self-describing format, so they work with formats such as bincode as well as JSON.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use alloc::string::String;
+
+use ::serde::de::{self, Deserialize, Deserializer, Visitor};
+use ::serde::ser::{Serialize, Serializer};
+
+use crate::checked::{Checked, StaticSystem};
+use crate::cn_resident_id::ResidentId;
+use crate::dyn_system::DynSystem;
+use crate::grid::Grid;
+use crate::isan::Isan;
+use crate::isni::Isni;
+use crate::lei::Lei;
+use crate::standard::StandardSystem;
+
+impl<S> Serialize for Checked<S> {
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        serializer.serialize_str(self)
+    }
+}
+
+impl<'de, S> Deserialize<'de> for Checked<S>
+where
+    S: StaticSystem,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Checked::new(value).map_err(de::Error::custom)
+    }
+}
+
+/// Deserialize a string and validate it with the system.
+fn deserialize_checked<'de, D>(deserializer: D, system: &dyn DynSystem) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    system.try_validate_str(&value).map_err(de::Error::custom)?;
+    Ok(value)
+}
+
+macro_rules! with_module {
+    ($module:ident, $system:ident) => {
+        #[doc = concat!("Use with `#[serde(with = \"iso_iec_7064::serde::", stringify!($module), "\")]` on a string field validated with `", stringify!($system), "`.")]
+        pub mod $module {
+            use ::serde::{Deserializer, Serializer};
+            use alloc::string::String;
+
+            /// Serialize the string as is.
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: AsRef<str> + ?Sized,
+                S: Serializer,
+            {
+                serializer.serialize_str(value.as_ref())
+            }
+
+            /// Deserialize a string, failing unless it satisfies the check.
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                super::deserialize_checked(deserializer, &crate::$system)
+            }
+        }
+    };
+}
+
+with_module!(mod_11_2, MOD_11_2);
+with_module!(mod_37_2, MOD_37_2);
+with_module!(mod_97_10, MOD_97_10);
+with_module!(mod_661_26, MOD_661_26);
+with_module!(mod_1271_36, MOD_1271_36);
+with_module!(mod_11_10, MOD_11_10);
+with_module!(mod_27_26, MOD_27_26);
+with_module!(mod_37_36, MOD_37_36);
+with_module!(mod_97_10_alphanumeric, MOD_97_10_ALPHANUMERIC);
+
+/// Parses a string with `FromStr`, describing the expected value with `name`.
+struct FromStrVisitor<T> {
+    name: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> Visitor<'_> for FromStrVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a valid {}", self.name)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        v.parse().map_err(E::custom)
+    }
+}
+
+macro_rules! impl_serde_via_str {
+    ($type:ty, $name:expr) => {
+        impl Serialize for $type {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_str(FromStrVisitor {
+                    name: $name,
+                    marker: PhantomData,
+                })
+            }
+        }
+    };
+}
+
+impl_serde_via_str!(Isni, "ISNI");
+impl_serde_via_str!(Grid, "GRid");
+impl_serde_via_str!(Isan, "ISAN");
+impl_serde_via_str!(Lei, "LEI");
+impl_serde_via_str!(ResidentId, "resident identity card number");
+
+impl Serialize for StandardSystem {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.system_name())
+    }
+}
+
+/// Accepts a designation number, or a name or designation number as a string.
+struct StandardSystemVisitor;
+
+impl Visitor<'_> for StandardSystemVisitor {
+    type Value = StandardSystem;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an ISO/IEC 7064 system name or designation")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<StandardSystem, E>
+    where
+        E: de::Error,
+    {
+        let designation =
+            u8::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))?;
+        crate::system_by_designation(designation).map_err(E::custom)
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<StandardSystem, E>
+    where
+        E: de::Error,
+    {
+        let v = u64::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))?;
+        self.visit_u64(v)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<StandardSystem, E>
+    where
+        E: de::Error,
+    {
+        v.parse().map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for StandardSystem {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StandardSystemVisitor)
+    }
+}
+
+/// Use with `#[serde(with = "iso_iec_7064::serde::designation")]` on a `StandardSystem` field to
+/// serialize it as its designation number, and deserialize it from one.
+pub mod designation {
+    use ::serde::{Deserializer, Serializer};
+
+    use crate::dyn_system::DynSystem;
+    use crate::standard::StandardSystem;
+
+    /// Serialize the system as its designation number.
+    pub fn serialize<S>(system: &StandardSystem, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u8(system.system_designation())
+    }
+
+    /// Deserialize a system from its designation number.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<StandardSystem, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_u8(super::StandardSystemVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checked::{CheckedMod11_2, CheckedMod97_10Alphanumeric};
+    use crate::cn_resident_id::ResidentId;
+    use crate::grid::Grid;
+    use crate::isan::Isan;
+    use crate::isni::Isni;
+    use crate::lei::Lei;
+    use crate::StandardSystem;
+
+    use alloc::string::String;
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn checked_round_trips_and_rejects_invalid() {
+        let checked: CheckedMod11_2 = serde_json::from_str(r#""079""#).unwrap();
+        assert_eq!("079", checked.as_str());
+        assert_eq!(r#""079""#, serde_json::to_string(&checked).unwrap());
+
+        let error = serde_json::from_str::<CheckedMod11_2>(r#""078""#).unwrap_err();
+        assert!(error.to_string().contains("check"), "{}", error);
+        assert!(
+            serde_json::from_str::<CheckedMod97_10Alphanumeric>(r#""WEST12345698765432GB82""#)
+                .is_ok()
+        );
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Record {
+        #[serde(with = "crate::serde::mod_11_10")]
+        id: String,
+        system: StandardSystem,
+        #[serde(with = "crate::serde::designation")]
+        fallback: StandardSystem,
+    }
+
+    #[test]
+    fn with_modules_validate_strings() {
+        let record: Record =
+            serde_json::from_str(r#"{"id": "07945", "system": "MOD 97-10", "fallback": 6}"#)
+                .unwrap();
+        assert_eq!(
+            Record {
+                id: "07945".into(),
+                system: StandardSystem::Mod97_10,
+                fallback: StandardSystem::Mod11_10,
+            },
+            record
+        );
+        assert_eq!(
+            r#"{"id":"07945","system":"ISO/IEC 7064, MOD 97-10","fallback":6}"#,
+            serde_json::to_string(&record).unwrap()
+        );
+
+        assert!(serde_json::from_str::<Record>(
+            r#"{"id": "07946", "system": "MOD 97-10", "fallback": 6}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn standard_system_from_designation_or_name() {
+        assert_eq!(
+            StandardSystem::Mod37_36,
+            serde_json::from_str::<StandardSystem>(r#""8""#).unwrap()
+        );
+        assert_eq!(
+            StandardSystem::Mod37_36,
+            serde_json::from_str::<StandardSystem>(r#""ISO/IEC 7064, MOD 37,36""#).unwrap()
+        );
+        assert!(serde_json::from_str::<StandardSystem>(r#""0""#).is_err());
+        assert!(serde_json::from_str::<StandardSystem>(r#""9""#).is_err());
+        assert!(serde_json::from_str::<StandardSystem>(r#""MOD 1-1""#).is_err());
+    }
+
+    #[test]
+    fn non_self_describing_formats() {
+        let record = Record {
+            id: "07945".into(),
+            system: StandardSystem::Mod97_10,
+            fallback: StandardSystem::Mod11_10,
+        };
+        let bytes = bincode::serialize(&record).unwrap();
+        assert_eq!(record, bincode::deserialize::<Record>(&bytes).unwrap());
+
+        let isni: Isni = "0000-0002-1825-0097".parse().unwrap();
+        let bytes = bincode::serialize(&isni).unwrap();
+        assert_eq!(isni, bincode::deserialize::<Isni>(&bytes).unwrap());
+    }
+
+    #[test]
+    fn identifiers_round_trip_through_strings() {
+        let isni: Isni =
+            serde_json::from_str(r#""https://orcid.org/0000-0002-1825-0097""#).unwrap();
+        assert_eq!(
+            r#""0000-0002-1825-0097""#,
+            serde_json::to_string(&isni).unwrap()
+        );
+
+        let grid: Grid = serde_json::from_str(r#""A12425GABC1234002M""#).unwrap();
+        assert_eq!(
+            r#""A1-2425G-ABC1234002-M""#,
+            serde_json::to_string(&grid).unwrap()
+        );
+
+        let isan: Isan = serde_json::from_str(r#""isan 00000001894700008""#).unwrap();
+        assert_eq!(
+            r#""ISAN 0000-0001-8947-0000-8""#,
+            serde_json::to_string(&isan).unwrap()
+        );
+
+        let lei: Lei = serde_json::from_str(r#""5493 001k jtii gc8y 1r12""#).unwrap();
+        assert_eq!(
+            r#""5493001KJTIIGC8Y1R12""#,
+            serde_json::to_string(&lei).unwrap()
+        );
+
+        let id: ResidentId = serde_json::from_str(r#""11010519491231002x""#).unwrap();
+        assert_eq!(
+            r#""11010519491231002X""#,
+            serde_json::to_string(&id).unwrap()
+        );
+
+        let error = serde_json::from_str::<Isni>(r#""0000-0002-1825-0098""#).unwrap_err();
+        assert!(error.to_string().contains("check"), "{}", error);
+        assert!(serde_json::from_str::<Lei>("5493").is_err());
+    }
+}
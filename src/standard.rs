@@ -0,0 +1,213 @@
+//! # iso_iec_7064::standard
+//!
+//! An enumeration of all the _Check Character Systems_ defined in The Standard, for selecting one
+//! at runtime.
+
+use crate::alphabet::Alphabet;
+use crate::dyn_system::DynSystem;
+use crate::error::{Error, LookupError};
+use crate::system::CheckChars;
+use crate::{
+    MOD_11_10, MOD_11_2, MOD_1271_36, MOD_27_26, MOD_37_2, MOD_37_36, MOD_661_26, MOD_97_10,
+};
+
+/// One of the eight _Check Character Systems_ defined in The Standard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StandardSystem {
+    /// ISO/IEC 7064, MOD 11-2 (designation 1)
+    Mod11_2,
+    /// ISO/IEC 7064, MOD 37-2 (designation 2)
+    Mod37_2,
+    /// ISO/IEC 7064, MOD 97-10 (designation 3)
+    Mod97_10,
+    /// ISO/IEC 7064, MOD 661-26 (designation 4)
+    Mod661_26,
+    /// ISO/IEC 7064, MOD 1271-36 (designation 5)
+    Mod1271_36,
+    /// ISO/IEC 7064, MOD 11,10 (designation 6)
+    Mod11_10,
+    /// ISO/IEC 7064, MOD 27,26 (designation 7)
+    Mod27_26,
+    /// ISO/IEC 7064, MOD 37,36 (designation 8)
+    Mod37_36,
+}
+
+impl StandardSystem {
+    /// All the _Check Character Systems_ defined in The Standard, in order of designation.
+    pub const ALL: [StandardSystem; 8] = [
+        StandardSystem::Mod11_2,
+        StandardSystem::Mod37_2,
+        StandardSystem::Mod97_10,
+        StandardSystem::Mod661_26,
+        StandardSystem::Mod1271_36,
+        StandardSystem::Mod11_10,
+        StandardSystem::Mod27_26,
+        StandardSystem::Mod37_36,
+    ];
+
+    /// Validate that the input ASCII bytes, which must already have the check digit(s) appended,
+    /// satisfy the check. This is a `const fn`, so it can be used at compile time.
+    pub const fn validate_ascii_bytes(self, bytes: &[u8]) -> bool {
+        match self {
+            StandardSystem::Mod11_2 => MOD_11_2.validate_ascii_bytes(bytes),
+            StandardSystem::Mod37_2 => MOD_37_2.validate_ascii_bytes(bytes),
+            StandardSystem::Mod97_10 => MOD_97_10.validate_ascii_bytes(bytes),
+            StandardSystem::Mod661_26 => MOD_661_26.validate_ascii_bytes(bytes),
+            StandardSystem::Mod1271_36 => MOD_1271_36.validate_ascii_bytes(bytes),
+            StandardSystem::Mod11_10 => MOD_11_10.validate_ascii_bytes(bytes),
+            StandardSystem::Mod27_26 => MOD_27_26.validate_ascii_bytes(bytes),
+            StandardSystem::Mod37_36 => MOD_37_36.validate_ascii_bytes(bytes),
+        }
+    }
+
+    /// Compute the checksum for payload ASCII bytes. This is a `const fn`, so it can be used at
+    /// compile time.
+    pub const fn checksum_ascii_bytes(self, bytes: &[u8]) -> Option<u16> {
+        match self {
+            StandardSystem::Mod11_2 => MOD_11_2.checksum_ascii_bytes(bytes),
+            StandardSystem::Mod37_2 => MOD_37_2.checksum_ascii_bytes(bytes),
+            StandardSystem::Mod97_10 => MOD_97_10.checksum_ascii_bytes(bytes),
+            StandardSystem::Mod661_26 => MOD_661_26.checksum_ascii_bytes(bytes),
+            StandardSystem::Mod1271_36 => MOD_1271_36.checksum_ascii_bytes(bytes),
+            StandardSystem::Mod11_10 => MOD_11_10.checksum_ascii_bytes(bytes),
+            StandardSystem::Mod27_26 => MOD_27_26.checksum_ascii_bytes(bytes),
+            StandardSystem::Mod37_36 => MOD_37_36.checksum_ascii_bytes(bytes),
+        }
+    }
+
+    /// The implementation of the Check character system, as a trait object.
+    pub fn as_dyn(self) -> &'static dyn DynSystem {
+        match self {
+            StandardSystem::Mod11_2 => &MOD_11_2,
+            StandardSystem::Mod37_2 => &MOD_37_2,
+            StandardSystem::Mod97_10 => &MOD_97_10,
+            StandardSystem::Mod661_26 => &MOD_661_26,
+            StandardSystem::Mod1271_36 => &MOD_1271_36,
+            StandardSystem::Mod11_10 => &MOD_11_10,
+            StandardSystem::Mod27_26 => &MOD_27_26,
+            StandardSystem::Mod37_36 => &MOD_37_36,
+        }
+    }
+}
+
+/// Parses either a designation (such as "3") or a name (such as "MOD 97-10"), using
+/// `system_by_designation` or `system_by_name` respectively.
+impl core::str::FromStr for StandardSystem {
+    type Err = LookupError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit()) {
+            match s.parse::<u8>() {
+                Ok(designation) => crate::system_by_designation(designation),
+                // Too large for a designation, so certainly not one The Standard assigns.
+                Err(_) => Err(LookupError::UnknownDesignation(
+                    s.parse().unwrap_or(u32::MAX),
+                )),
+            }
+        } else {
+            crate::system_by_name(s)
+        }
+    }
+}
+
+impl DynSystem for StandardSystem {
+    fn system_name(&self) -> &'static str {
+        self.as_dyn().system_name()
+    }
+
+    fn system_designation(&self) -> u8 {
+        self.as_dyn().system_designation()
+    }
+
+    fn system_alphabet(&self) -> Alphabet {
+        self.as_dyn().system_alphabet()
+    }
+
+    fn system_check_length(&self) -> u8 {
+        self.as_dyn().system_check_length()
+    }
+
+    fn validate_bytes(&self, bytes: &[u8]) -> bool {
+        self.as_dyn().validate_bytes(bytes)
+    }
+
+    fn try_validate_bytes(&self, bytes: &[u8]) -> Result<(), Error> {
+        self.as_dyn().try_validate_bytes(bytes)
+    }
+
+    fn checksum_bytes(&self, bytes: &[u8]) -> Option<u16> {
+        self.as_dyn().checksum_bytes(bytes)
+    }
+
+    fn try_checksum_bytes(&self, bytes: &[u8]) -> Result<u16, Error> {
+        self.as_dyn().try_checksum_bytes(bytes)
+    }
+
+    fn render_check_chars(&self, checksum: u16) -> Option<CheckChars> {
+        self.as_dyn().render_check_chars(checksum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dyn_system::DynSystem;
+    use crate::standard::StandardSystem;
+
+    #[test]
+    fn all_is_in_designation_order() {
+        for (i, system) in StandardSystem::ALL.iter().enumerate() {
+            assert_eq!((i + 1) as u8, system.system_designation());
+        }
+    }
+
+    #[test]
+    fn enum_delegates_to_system() {
+        assert_eq!(
+            "ISO/IEC 7064, MOD 97-10",
+            StandardSystem::Mod97_10.system_name()
+        );
+        assert!(StandardSystem::Mod97_10.validate_str("79444"));
+        assert!(StandardSystem::Mod11_10.validate_str("07945"));
+        assert_eq!(Some(140), StandardSystem::Mod1271_36.checksum_str("ISO79"));
+    }
+
+    #[test]
+    fn const_fns_agree_with_dyn_system() {
+        let inputs = [
+            "",
+            "0",
+            "07940",
+            "079X",
+            "X7940",
+            "79444",
+            "ISO793W",
+            "ABCDEFRV",
+            "07945",
+            "G123498654321H",
+            "0794",
+            "ISO79",
+            "ABCDEF",
+            "a1",
+            "*",
+        ];
+        for system in StandardSystem::ALL {
+            for input in inputs {
+                assert_eq!(
+                    system.validate_str(input),
+                    system.validate_ascii_bytes(input.as_bytes()),
+                    "{:?} {:?}",
+                    system,
+                    input
+                );
+                assert_eq!(
+                    system.checksum_str(input),
+                    system.checksum_ascii_bytes(input.as_bytes()),
+                    "{:?} {:?}",
+                    system,
+                    input
+                );
+            }
+        }
+    }
+}
@@ -2,11 +2,18 @@
 //!
 //! Trait implemented by all _Check Character Systems_, both pure and hybrid.
 
-/// There are five _Alphabets_ used by the _Check Character Systems_ defined in The Standard. While
-/// The Standard does not specify a character encoding, we will be using the ASCII subset of Unicode.
-/// In this encoding, every character of every _Alphabet_ corresponds to a single byte of an input
-/// string, allowing us to implement validation and computation operations on raw byte sequences,
-/// without any overhead from handling UTF-8.
+use crate::alphabet::Alphabet;
+use crate::error::Error;
+use crate::normalize::Normalizer;
+
+#[cfg(feature = "alloc")]
+use crate::correction::{self, Correction};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// Every character of every _Alphabet_ corresponds to a single byte of an input string, allowing
+/// us to implement validation and computation operations on raw byte sequences, without any
+/// overhead from handling UTF-8.
 ///
 /// The five _Alphabets_ are distinguished by their maximum digit value, and the `char_value`
 /// function takes the max digit value as a generic parameter.
@@ -14,40 +21,88 @@
 /// Convert an input ASCII character into its corresponding numeric value, returning -1 if the
 /// input value is out of range.
 pub const fn char_value<const MAX_DIGIT_VALUE: u8>(c: u8) -> i8 {
-    match MAX_DIGIT_VALUE {
-        // aka "numeric"
-        9 => match c {
-            b'0'..=b'9' => (c - b'0') as i8,
-            _ => -1i8,
-        },
-        // aka "numeric with 'x'"
-        10 => match c {
-            b'0'..=b'9' => (c - b'0') as i8,
-            b'X' => 10i8,
-            _ => -1i8,
-        },
-        // aka "alphabetic"
-        25 => match c {
-            b'A'..=b'Z' => (c - b'A') as i8,
-            _ => -1i8,
-        },
-        // aka "alphanumeric"
-        35 => match c {
-            b'0'..=b'9' => (c - b'0') as i8,
-            b'A'..=b'Z' => (c - b'A' + 10) as i8,
-            _ => -1i8,
-        },
-        // aka "alphanumeric with '*'"
-        36 => match c {
-            b'0'..=b'9' => (c - b'0') as i8,
-            b'A'..=b'Z' => (c - b'A' + 10) as i8,
-            b'*' => 36i8,
-            _ => -1i8,
+    match Alphabet::from_max_digit_value(MAX_DIGIT_VALUE) {
+        Some(alphabet) => match alphabet.char_value(c) {
+            Some(v) => v as i8,
+            None => -1i8,
         },
-        _ => -1i8, // Compiler won't let us panic!() here
+        None => -1i8, // Compiler won't let us panic!() here
+    }
+}
+
+/// The check character(s) produced by a _Check Character System_. No system in The Standard
+/// produces more than two check characters, so these are held inline without allocating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CheckChars {
+    bytes: [u8; 2],
+    len: u8,
+}
+
+impl CheckChars {
+    /// Construct from one or two ASCII bytes. Returns `None` for any other length or for
+    /// non-ASCII bytes.
+    pub fn from_ascii_bytes(bytes: &[u8]) -> Option<CheckChars> {
+        if bytes.is_empty() || bytes.len() > 2 || !bytes.is_ascii() {
+            return None;
+        }
+        let mut check_chars = CheckChars {
+            bytes: [0; 2],
+            len: bytes.len() as u8,
+        };
+        check_chars.bytes[..bytes.len()].copy_from_slice(bytes);
+        Some(check_chars)
+    }
+
+    /// The check character(s) as ASCII bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    /// The check character(s) as a string slice.
+    pub fn as_str(&self) -> &str {
+        // Only ever constructed from ASCII bytes, which are always valid UTF-8.
+        core::str::from_utf8(self.as_bytes()).expect("check characters are ASCII")
     }
 }
 
+impl core::fmt::Display for CheckChars {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Find the first character that is outside the ALPHABET, or that is a _Supplementary Check
+/// Character_ within the first `payload_length` bytes.
+fn malformed(alphabet: Alphabet, bytes: &[u8], payload_length: usize) -> Option<Error> {
+    bytes
+        .iter()
+        .enumerate()
+        .find_map(|(position, &byte)| match alphabet.char_value(byte) {
+            None => Some(Error::IllegalCharacter { position, byte }),
+            Some(v) if position < payload_length && v >= alphabet.radix() => {
+                Some(Error::SupplementaryCharacterInPayload { position, byte })
+            }
+            Some(_) => None,
+        })
+}
+
+/// Compute the check character for a payload known to be in the ALPHABET, with a system that
+/// produces a single check character. Used by the identifier modules once they have checked the
+/// format of the payload.
+pub(crate) fn check_char<const MAX_DIGIT_VALUE: u8, S, I>(system: &S, payload: I) -> u8
+where
+    S: System<MAX_DIGIT_VALUE> + ?Sized,
+    I: IntoIterator<Item = u8>,
+{
+    let checksum = system
+        .checksum_ascii_bytes_iter(payload)
+        .expect("payload is in the alphabet");
+    system
+        .check_chars(checksum)
+        .expect("checksum is always representable")
+        .as_bytes()[0]
+}
+
 /// Trait implemented by all _Check Character Systems_
 pub trait System<const MAX_DIGIT_VALUE: u8> {
     /// In The Standard, Section 5.4.2, Table 3 "Single digit designations" specifies official names
@@ -60,9 +115,13 @@ pub trait System<const MAX_DIGIT_VALUE: u8> {
     /// zero is reserved for "No check character or non-standard system".
     fn designation(&self) -> u8;
 
-    // /// The alphabet used in the Check character system, including the symbols allowed in the
-    // /// payload and any additional symbols that may be allowed in the check character(s).
-    // fn alphabet(&self) -> &Alphabet;
+    /// The alphabet used in the Check character system, including the symbols allowed in the
+    /// payload and any additional symbols that may be allowed in the check character(s). By
+    /// default, this is the _Alphabet_ identified by `MAX_DIGIT_VALUE`.
+    fn alphabet(&self) -> Alphabet {
+        Alphabet::from_max_digit_value(MAX_DIGIT_VALUE)
+            .expect("MAX_DIGIT_VALUE identifies one of the alphabets of The Standard")
+    }
 
     /// The number of check characters the Check character system produces.
     fn check_length(&self) -> u8;
@@ -101,6 +160,48 @@ pub trait System<const MAX_DIGIT_VALUE: u8> {
         self.validate_ascii_bytes_iter(it)
     }
 
+    /// Validate that the input ASCII bytes, which must already have the check digit(s) appended,
+    /// satisfy the check, reporting the reason if they do not. The default implementation
+    /// validates with `validate_ascii_bytes_iter` and then diagnoses any failure, so systems can
+    /// override it to do both in one pass. Input rejected for reasons other than those in `Error`,
+    /// or whose check characters `CheckChars` cannot hold, is reported as `Error::Rejected`.
+    fn try_validate_ascii_bytes(&self, bytes: &[u8]) -> Result<(), Error> {
+        if self.validate_ascii_bytes_iter(bytes.iter().copied()) {
+            return Ok(());
+        }
+
+        // There must be at least one Payload character before the check character(s).
+        let check_length = self.check_length() as usize;
+        if bytes.len() <= check_length {
+            return Err(Error::TooShort {
+                minimum: check_length + 1,
+                length: bytes.len(),
+            });
+        }
+
+        let payload_length = bytes.len() - check_length;
+        if let Some(error) = malformed(self.alphabet(), bytes, payload_length) {
+            return Err(error);
+        }
+
+        let checksum = self.try_checksum_ascii_bytes(&bytes[..payload_length])?;
+        match (
+            self.check_chars(checksum),
+            CheckChars::from_ascii_bytes(&bytes[payload_length..]),
+        ) {
+            (Some(expected), Some(found)) if expected != found => {
+                Err(Error::CheckMismatch { expected, found })
+            }
+            _ => Err(Error::Rejected),
+        }
+    }
+
+    /// Check that the input string, which must already have the check digit(s) appended, satisfies
+    /// the check, reporting the reason if it does not.
+    fn try_validate_string(&self, string: &str) -> Result<(), Error> {
+        self.try_validate_ascii_bytes(string.as_bytes())
+    }
+
     /// Compute the checksum for an iterator of payload digit values (for example, values in the
     /// range 0 to 9 inclusive for `Alphabet::Numeric`). If a digit value outside those allowed by
     /// the ALPHABET is encountered, returns None immediately.
@@ -133,4 +234,258 @@ pub trait System<const MAX_DIGIT_VALUE: u8> {
         let it = string.as_bytes().iter().copied();
         self.checksum_ascii_bytes_iter(it)
     }
+
+    /// Compute the checksum for payload ASCII bytes, reporting the reason if there is none. The
+    /// default implementation computes with `checksum_ascii_bytes_iter` and then diagnoses any
+    /// failure, so systems can override it to do both in one pass. Payloads rejected for reasons
+    /// other than those in `Error` are reported as `Error::Rejected`.
+    fn try_checksum_ascii_bytes(&self, bytes: &[u8]) -> Result<u16, Error> {
+        if let Some(checksum) = self.checksum_ascii_bytes_iter(bytes.iter().copied()) {
+            return Ok(checksum);
+        }
+
+        // There must be at least one Payload character.
+        if bytes.is_empty() {
+            return Err(Error::TooShort {
+                minimum: 1,
+                length: 0,
+            });
+        }
+
+        match malformed(self.alphabet(), bytes, bytes.len()) {
+            Some(error) => Err(error),
+            None => Err(Error::Rejected),
+        }
+    }
+
+    /// Compute the check digit for a payload string, reporting the reason if there is none.
+    fn try_checksum_string(&self, string: &str) -> Result<u16, Error> {
+        self.try_checksum_ascii_bytes(string.as_bytes())
+    }
+
+    /// Check that the input string, which must already have the check digit(s) appended, satisfies
+    /// the check after normalization. In strict mode, returns false if any character would be
+    /// stripped.
+    fn validate_normalized(&self, string: &str, normalizer: &Normalizer) -> bool {
+        let bytes = string.as_bytes();
+        if normalizer.check_strict(bytes).is_err() {
+            return false;
+        }
+        self.validate_ascii_bytes_iter(normalizer.normalize_iter(bytes))
+    }
+
+    /// Compute the checksum for a payload string after normalization. In strict mode, returns None
+    /// if any character would be stripped.
+    fn checksum_normalized(&self, string: &str, normalizer: &Normalizer) -> Option<u16> {
+        let bytes = string.as_bytes();
+        if normalizer.check_strict(bytes).is_err() {
+            return None;
+        }
+        self.checksum_ascii_bytes_iter(normalizer.normalize_iter(bytes))
+    }
+
+    /// Check that the input string satisfies the check after normalization, reporting the reason
+    /// if it does not. Positions in the error refer to the input string, before normalization.
+    #[cfg(feature = "alloc")]
+    fn try_validate_normalized(&self, string: &str, normalizer: &Normalizer) -> Result<(), Error> {
+        normalizer.check_strict(string.as_bytes())?;
+        let normalized = normalizer.normalize(string);
+        self.try_validate_string(normalized.as_str())
+            .map_err(|error| normalized.original_error(error))
+    }
+
+    /// Compute the checksum for a payload string after normalization, reporting the reason if
+    /// there is none. Positions in the error refer to the input string, before normalization.
+    #[cfg(feature = "alloc")]
+    fn try_checksum_normalized(&self, string: &str, normalizer: &Normalizer) -> Result<u16, Error> {
+        normalizer.check_strict(string.as_bytes())?;
+        let normalized = normalizer.normalize(string);
+        self.try_checksum_string(normalized.as_str())
+            .map_err(|error| normalized.original_error(error))
+    }
+
+    /// Render a checksum (as returned by `checksum_string` and friends) as `check_length()` check
+    /// characters. When there are two check characters, the checksum is written as two digits in
+    /// the radix of the payload symbols of the ALPHABET (so 140 becomes "3W" for MOD 1271-36).
+    /// Returns None if the checksum cannot be represented by this system.
+    fn check_chars(&self, checksum: u16) -> Option<CheckChars> {
+        let alphabet = self.alphabet();
+        let radix = alphabet.radix() as u16;
+
+        let mut bytes = [0u8; 2];
+        match self.check_length() {
+            1 => {
+                bytes[0] = alphabet.value_char(u8::try_from(checksum).ok()?)?;
+                CheckChars::from_ascii_bytes(&bytes[..1])
+            }
+            2 => {
+                if checksum >= radix * radix {
+                    return None;
+                }
+                bytes[0] = alphabet.value_char((checksum / radix) as u8)?;
+                bytes[1] = alphabet.value_char((checksum % radix) as u8)?;
+                CheckChars::from_ascii_bytes(&bytes)
+            }
+            _ => None,
+        }
+    }
+
+    /// Compute the check character(s) for a payload string. If characters outside the ALPHABET are
+    /// encountered, returns None immediately.
+    fn check_chars_string(&self, string: &str) -> Option<CheckChars> {
+        self.checksum_string(string)
+            .and_then(|checksum| self.check_chars(checksum))
+    }
+
+    /// Compute the check character(s) for a payload string and return the complete string, with
+    /// the check character(s) appended to the payload. If characters outside the ALPHABET are
+    /// encountered, returns None immediately.
+    #[cfg(feature = "alloc")]
+    fn complete_string(&self, string: &str) -> Option<String> {
+        let check_chars = self.check_chars_string(string)?;
+        let mut complete = String::with_capacity(string.len() + check_chars.as_bytes().len());
+        complete.push_str(string);
+        complete.push_str(check_chars.as_str());
+        Some(complete)
+    }
+
+    /// For an input that fails the check, suggest the strings within one substitution or one
+    /// adjacent transposition of it that satisfy the check, substitutions first. Returns nothing
+    /// if the input is valid. See the `correction` module.
+    #[cfg(feature = "alloc")]
+    fn suggest_corrections(&self, string: &str) -> Vec<Correction> {
+        correction::suggest(self, string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system::*;
+    use crate::MOD_11_2;
+
+    /// A system implemented outside the crate, providing only the required methods.
+    struct External;
+
+    impl System<10> for External {
+        fn name(&self) -> &'static str {
+            "External"
+        }
+
+        fn designation(&self) -> u8 {
+            0
+        }
+
+        fn check_length(&self) -> u8 {
+            1
+        }
+
+        fn validate_digit_values_iter<I>(&self, it: I) -> bool
+        where
+            I: IntoIterator<Item = u8>,
+        {
+            MOD_11_2.validate_digit_values_iter(it)
+        }
+
+        fn checksum_digit_values_iter<I>(&self, it: I) -> Option<u16>
+        where
+            I: IntoIterator<Item = u8>,
+        {
+            MOD_11_2.checksum_digit_values_iter(it)
+        }
+    }
+
+    /// A system implemented outside the crate with a rule of its own: payloads are at most three
+    /// characters long. It can also claim more check characters than `CheckChars` holds.
+    struct Restricted {
+        check_length: u8,
+    }
+
+    impl System<10> for Restricted {
+        fn name(&self) -> &'static str {
+            "Restricted"
+        }
+
+        fn designation(&self) -> u8 {
+            0
+        }
+
+        fn check_length(&self) -> u8 {
+            self.check_length
+        }
+
+        fn validate_digit_values_iter<I>(&self, it: I) -> bool
+        where
+            I: IntoIterator<Item = u8>,
+        {
+            let mut length = 0;
+            let valid =
+                MOD_11_2.validate_digit_values_iter(it.into_iter().inspect(|_| length += 1));
+            valid && length <= 3 + self.check_length as usize
+        }
+
+        fn checksum_digit_values_iter<I>(&self, it: I) -> Option<u16>
+        where
+            I: IntoIterator<Item = u8>,
+        {
+            let mut length = 0;
+            let checksum =
+                MOD_11_2.checksum_digit_values_iter(it.into_iter().inspect(|_| length += 1));
+            checksum.filter(|_| length <= 3)
+        }
+    }
+
+    #[test]
+    fn default_alphabet_follows_max_digit_value() {
+        assert_eq!(Alphabet::NumericWithX, External.alphabet());
+        assert_eq!(
+            Some(CheckChars::from_ascii_bytes(b"X").unwrap()),
+            External.check_chars(10)
+        );
+        assert!(External.validate_string("079X"));
+    }
+
+    #[test]
+    fn default_try_methods_match_the_standard_systems() {
+        for input in ["079X", "0794", "07X4", "079", "07A4", "7", "X", ""] {
+            assert_eq!(
+                MOD_11_2.try_validate_string(input),
+                External.try_validate_string(input),
+                "{}",
+                input
+            );
+            assert_eq!(
+                MOD_11_2.try_checksum_string(input),
+                External.try_checksum_string(input),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(Ok(10), External.try_checksum_string("079"));
+    }
+
+    #[test]
+    fn default_try_methods_report_rules_of_external_systems() {
+        let restricted = Restricted { check_length: 1 };
+        assert_eq!(Ok(()), restricted.try_validate_string("079X"));
+        assert_eq!(Err(Error::Rejected), restricted.try_checksum_string("0794"));
+        assert_eq!(
+            Err(Error::Rejected),
+            restricted.try_validate_string("07940")
+        );
+        assert_eq!(
+            Err(Error::IllegalCharacter {
+                position: 1,
+                byte: b'A'
+            }),
+            restricted.try_checksum_string("0A94")
+        );
+
+        // Three check characters cannot be represented by `CheckChars`.
+        let restricted = Restricted { check_length: 3 };
+        assert_eq!(Err(Error::Rejected), restricted.try_validate_string("0790"));
+        assert_eq!(
+            Err(Error::Rejected),
+            restricted.try_validate_string("079XXX")
+        );
+    }
 }